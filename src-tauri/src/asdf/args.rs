@@ -0,0 +1,201 @@
+use crate::asdf::lint::is_valid_plugin_name;
+use crate::error::AppError;
+
+/// A validated plugin name, safe to pass as an asdf positional argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginName(String);
+
+impl PluginName {
+    pub fn parse(value: impl Into<String>) -> Result<Self, AppError> {
+        let value = value.into();
+        check_arg("plugin name", &value)?;
+        if !is_valid_plugin_name(&value) {
+            return Err(AppError::ParseError(format!(
+                "invalid plugin name {value:?}: use lowercase letters, digits, '-' and '_'"
+            )));
+        }
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A validated version, version prefix or `ref:`/`path:` spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionString(String);
+
+impl VersionString {
+    pub fn parse(value: impl Into<String>) -> Result<Self, AppError> {
+        let value = value.into();
+        check_arg("version", &value)?;
+        Ok(Self(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Reject values asdf could read as a flag or that would be split or
+/// mangled on the way through: empty, leading `-`, whitespace or control
+/// characters.
+pub fn check_arg(what: &str, value: &str) -> Result<(), AppError> {
+    let reason = if value.is_empty() {
+        "must not be empty"
+    } else if value.starts_with('-') {
+        "must not start with '-'"
+    } else if value.chars().any(char::is_whitespace) {
+        "must not contain whitespace"
+    } else if value.chars().any(char::is_control) {
+        "must not contain control characters"
+    } else {
+        return Ok(());
+    };
+    Err(AppError::ParseError(format!(
+        "invalid {what} {value:?}: {reason}"
+    )))
+}
+
+/// Validate `(tool, version)` pairs, reporting every invalid one at once.
+pub fn parse_pairs(
+    pairs: &[(String, String)],
+) -> Result<Vec<(PluginName, VersionString)>, AppError> {
+    let mut parsed = Vec::new();
+    let mut invalid = Vec::new();
+    for (tool, version) in pairs {
+        match (
+            PluginName::parse(tool.as_str()),
+            VersionString::parse(version.as_str()),
+        ) {
+            (Ok(tool), Ok(version)) => parsed.push((tool, version)),
+            (Err(e), _) | (_, Err(e)) => invalid.push(e.to_string()),
+        }
+    }
+    if !invalid.is_empty() {
+        return Err(AppError::ParseError(invalid.join("; ")));
+    }
+    Ok(parsed)
+}
+
+/// `head -- positionals`: after the separator asdf treats every value as a
+/// positional argument, even ones that look like flags. Flags belong in
+/// `head`. The executor drops the separator for asdf releases that do not
+/// accept it (see `accepts_separator`); validated values are safe either
+/// way.
+pub fn with_positionals<'a>(head: &[&'a str], positionals: &[&'a str]) -> Vec<&'a str> {
+    let mut args = head.to_vec();
+    if !positionals.is_empty() {
+        args.push("--");
+        args.extend_from_slice(positionals);
+    }
+    args
+}
+
+/// Whether `asdf version` output names a release that reads `--` as the
+/// end of flags. The Go rewrite (0.16 and later) does; the earlier bash
+/// releases take it as the first positional, e.g. the plugin name.
+pub fn accepts_separator(version_output: &str) -> bool {
+    let Some(start) = version_output.find(|c: char| c.is_ascii_digit()) else {
+        return false;
+    };
+    let mut parts = version_output[start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|p| p.parse::<u64>().unwrap_or(0));
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    (major, minor) >= (0, 16)
+}
+
+/// `args` without the separator `with_positionals` added.
+pub fn drop_separator<'a>(args: &[&'a str]) -> Vec<&'a str> {
+    args.iter().copied().filter(|a| *a != "--").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_name_rejects_flags_and_junk() {
+        assert!(PluginName::parse("nodejs").is_ok());
+        assert!(PluginName::parse("golang-ci_lint").is_ok());
+        for bad in [
+            "",
+            "--all",
+            "-h",
+            "node js",
+            "node\n",
+            "node\u{7}",
+            "Node",
+            "../x",
+        ] {
+            assert!(PluginName::parse(bad).is_err(), "accepted {bad:?}");
+        }
+    }
+
+    #[test]
+    fn test_version_string_rejects_flags_and_junk() {
+        for ok in [
+            "20.11.0",
+            "latest:3.12",
+            "ref:v1.2.0",
+            "system",
+            "1.2.0-rc.1",
+        ] {
+            assert!(VersionString::parse(ok).is_ok(), "rejected {ok:?}");
+        }
+        for bad in ["", "--keep-download", "-1", "20 --all", "20\t", "20\0"] {
+            assert!(VersionString::parse(bad).is_err(), "accepted {bad:?}");
+        }
+    }
+
+    #[test]
+    fn test_with_positionals() {
+        assert_eq!(
+            with_positionals(&["install", "--keep-download"], &["nodejs", "20.11.0"]),
+            vec!["install", "--keep-download", "--", "nodejs", "20.11.0"]
+        );
+        assert_eq!(with_positionals(&["current"], &[]), vec!["current"]);
+        assert_eq!(
+            drop_separator(&["install", "--keep-download", "--", "nodejs", "20.11.0"]),
+            vec!["install", "--keep-download", "nodejs", "20.11.0"]
+        );
+    }
+
+    #[test]
+    fn test_accepts_separator() {
+        assert!(accepts_separator("asdf version 0.16.7\n"));
+        assert!(accepts_separator("v0.18.0 (revision 2f4e5a8)"));
+        assert!(accepts_separator("1.0.0"));
+        assert!(!accepts_separator("v0.14.1-f00f759\n"));
+        assert!(!accepts_separator("v0.15.0"));
+        assert!(!accepts_separator("unknown"));
+    }
+
+    #[test]
+    fn test_parse_pairs_reports_every_invalid_pair() {
+        let pairs = |items: &[(&str, &str)]| -> Vec<(String, String)> {
+            items
+                .iter()
+                .map(|(t, v)| (t.to_string(), v.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            parse_pairs(&pairs(&[("nodejs", "20.11.0"), ("ruby", "ref:v3.3.0")]))
+                .unwrap()
+                .len(),
+            2
+        );
+        let Err(AppError::ParseError(message)) = parse_pairs(&pairs(&[
+            ("nodejs", "20.11.0"),
+            ("-x", "1.0"),
+            ("python", "--keep-download"),
+        ])) else {
+            panic!("invalid pairs accepted");
+        };
+        assert!(message.contains("\"-x\""));
+        assert!(message.contains("\"--keep-download\""));
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::asdf::args::with_positionals;
+use crate::asdf::executor::{run_asdf, run_asdf_streaming};
+use crate::types::{BatchEvent, BatchSummary, JobStatus, JobUpdate};
+
+/// What installing a list of `(tool, version)` pairs takes.
+#[derive(Debug, Default, PartialEq)]
+pub struct InstallPlan {
+    /// Tools whose plugin has to be added first.
+    pub missing_plugins: Vec<String>,
+    pub missing: Vec<(String, String)>,
+    pub installed: Vec<(String, String)>,
+}
+
+/// Sort `pairs` into what is installed and what is not. `installed` maps
+/// each tool that has a plugin to its installed versions; tools absent from
+/// it need their plugin added. Duplicates are dropped, and `system` and
+/// `path:` versions have nothing to install.
+pub fn plan_installs(
+    pairs: &[(String, String)],
+    installed: &HashMap<String, Vec<String>>,
+) -> InstallPlan {
+    let mut plan = InstallPlan::default();
+    let mut seen = HashSet::new();
+    for (tool, version) in pairs {
+        if version == "system" || version.starts_with("path:") || !seen.insert((tool, version)) {
+            continue;
+        }
+        let pair = (tool.clone(), version.clone());
+        match installed.get(tool) {
+            None => {
+                if !plan.missing_plugins.contains(tool) {
+                    plan.missing_plugins.push(tool.clone());
+                }
+                plan.missing.push(pair);
+            }
+            Some(versions) if versions.contains(version) => plan.installed.push(pair),
+            Some(_) => plan.missing.push(pair),
+        }
+    }
+    plan
+}
+
+/// Runs asdf jobs one at a time, reporting each as a tracked sub-job and
+/// collecting results instead of stopping at the first failure.
+pub struct BatchRunner<F> {
+    emit: F,
+    jobs: Vec<JobUpdate>,
+}
+
+impl<F> BatchRunner<F>
+where
+    F: Fn(BatchEvent) + Clone + Send + 'static,
+{
+    pub fn new(emit: F) -> Self {
+        Self {
+            emit,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// `asdf plugin add <name>`; returns whether it succeeded.
+    pub async fn add_plugin(&self, name: &str, asdf_path: Option<&str>) -> bool {
+        let result = run_asdf(
+            &with_positionals(&["plugin", "add"], &[name]),
+            None,
+            asdf_path,
+        )
+        .await;
+        (self.emit)(BatchEvent::Plugin {
+            name: name.to_string(),
+            success: result.is_ok(),
+            message: result.as_ref().err().map(|e| e.to_string()),
+        });
+        result.is_ok()
+    }
+
+    /// Record a job that will not run.
+    pub fn skip(&mut self, tool: &str, version: &str, message: String) {
+        self.record(tool, version, JobStatus::Skipped, Some(message));
+    }
+
+    /// `asdf install <tool> <version>`, streaming its output tagged with the job.
+    pub async fn install(
+        &mut self,
+        tool: &str,
+        version: &str,
+        cwd: Option<&str>,
+        asdf_path: Option<&str>,
+        keep_download: bool,
+    ) {
+        let flags: &[&str] = if keep_download {
+            &["install", "--keep-download"]
+        } else {
+            &["install"]
+        };
+        let args = with_positionals(flags, &[tool, version]);
+        self.run(tool, version, &args, cwd, asdf_path).await;
+    }
+
+    /// `asdf uninstall <tool> <version>`.
+    pub async fn uninstall(&mut self, tool: &str, version: &str, asdf_path: Option<&str>) {
+        let args = with_positionals(&["uninstall"], &[tool, version]);
+        self.run(tool, version, &args, None, asdf_path).await;
+    }
+
+    async fn run(
+        &mut self,
+        tool: &str,
+        version: &str,
+        args: &[&str],
+        cwd: Option<&str>,
+        asdf_path: Option<&str>,
+    ) {
+        (self.emit)(BatchEvent::Job(JobUpdate {
+            tool: tool.to_string(),
+            version: version.to_string(),
+            status: JobStatus::Running,
+            message: None,
+        }));
+
+        let emit = self.emit.clone();
+        let (t, v) = (tool.to_string(), version.to_string());
+        let result = run_asdf_streaming(args, cwd, asdf_path, move |event| {
+            emit(BatchEvent::Output {
+                tool: t.clone(),
+                version: v.clone(),
+                event,
+            });
+        })
+        .await;
+
+        match result {
+            Ok(()) => self.record(tool, version, JobStatus::Succeeded, None),
+            Err(e) => self.record(tool, version, JobStatus::Failed, Some(e.to_string())),
+        }
+    }
+
+    fn record(&mut self, tool: &str, version: &str, status: JobStatus, message: Option<String>) {
+        let update = JobUpdate {
+            tool: tool.to_string(),
+            version: version.to_string(),
+            status,
+            message,
+        };
+        (self.emit)(BatchEvent::Job(update.clone()));
+        self.jobs.push(update);
+    }
+
+    /// Emit and return the overall summary.
+    pub fn finish(self) -> BatchSummary {
+        let count = |status: JobStatus| self.jobs.iter().filter(|j| j.status == status).count();
+        let summary = BatchSummary {
+            succeeded: count(JobStatus::Succeeded),
+            failed: count(JobStatus::Failed),
+            skipped: count(JobStatus::Skipped),
+            jobs: self.jobs.clone(),
+        };
+        (self.emit)(BatchEvent::Summary(summary.clone()));
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::asdf::args::parse_pairs;
+    use crate::error::AppError;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(t, v)| (t.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_installs_finds_missing_plugins_and_versions() {
+        let installed = HashMap::from([
+            ("nodejs".to_string(), vec!["20.11.0".to_string()]),
+            ("python".to_string(), Vec::new()),
+        ]);
+        let plan = plan_installs(
+            &pairs(&[
+                ("nodejs", "20.11.0"),
+                ("nodejs", "18.19.0"),
+                ("python", "3.12.1"),
+                ("python", "3.12.1"),
+                ("deno", "2.1.4"),
+                ("python", "system"),
+                ("ruby", "path:/opt/ruby"),
+            ]),
+            &installed,
+        );
+        assert_eq!(plan.missing_plugins, vec!["deno"]);
+        assert_eq!(
+            plan.missing,
+            pairs(&[
+                ("nodejs", "18.19.0"),
+                ("python", "3.12.1"),
+                ("deno", "2.1.4")
+            ])
+        );
+        assert_eq!(plan.installed, pairs(&[("nodejs", "20.11.0")]));
+    }
+
+    #[test]
+    fn test_invalid_pairs_are_rejected_before_planning() {
+        let requested = pairs(&[
+            ("nodejs", "20.11.0"),
+            ("nodejs", "--keep-download"),
+            ("python", "3.12.1"),
+        ]);
+        assert!(matches!(
+            parse_pairs(&requested),
+            Err(AppError::ParseError(_))
+        ));
+
+        let requested = pairs(&[
+            ("nodejs", "20.11.0"),
+            ("nodejs", "18.19.0"),
+            ("nodejs", "ref:abc123"),
+        ]);
+        let valid: Vec<(String, String)> = parse_pairs(&requested)
+            .unwrap()
+            .into_iter()
+            .map(|(t, v)| (t.as_str().to_string(), v.as_str().to_string()))
+            .collect();
+        let installed = HashMap::from([("nodejs".to_string(), vec!["18.19.0".to_string()])]);
+        let plan = plan_installs(&valid, &installed);
+        assert!(plan.missing_plugins.is_empty());
+        assert_eq!(
+            plan.missing,
+            pairs(&[("nodejs", "20.11.0"), ("nodejs", "ref:abc123")])
+        );
+        assert_eq!(plan.installed, pairs(&[("nodejs", "18.19.0")]));
+    }
+
+    #[test]
+    fn test_finish_counts_jobs() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mut runner = BatchRunner::new(move |event| sink.lock().unwrap().push(event));
+        runner.record("nodejs", "20.11.0", JobStatus::Succeeded, None);
+        runner.record(
+            "python",
+            "3.12.1",
+            JobStatus::Failed,
+            Some("boom".to_string()),
+        );
+        runner.record("ruby", "3.3.0", JobStatus::Succeeded, None);
+        runner.skip("deno", "2.1.4", "plugin deno is not installed".to_string());
+
+        let summary = runner.finish();
+        assert_eq!(
+            (summary.succeeded, summary.failed, summary.skipped),
+            (2, 1, 1)
+        );
+        assert_eq!(summary.jobs.len(), 4);
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(events.last(), Some(BatchEvent::Summary(s)) if s.failed == 1));
+    }
+}
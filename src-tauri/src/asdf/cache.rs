@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Unfiltered `asdf list all <name>` results, keyed by plugin name.
+/// Populated by the `list_all` command so other features can consult
+/// available versions without going back to the network.
+static LIST_ALL_CACHE: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+
+fn list_all_cache() -> &'static Mutex<HashMap<String, Vec<String>>> {
+    LIST_ALL_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Cached available versions for a plugin, if `list_all` has been fetched.
+pub fn cached_list_all(name: &str) -> Option<Vec<String>> {
+    list_all_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(name).cloned())
+}
+
+/// Remember the full available-versions list for a plugin.
+pub fn store_list_all(name: &str, versions: &[String]) {
+    if let Ok(mut cache) = list_all_cache().lock() {
+        cache.insert(name.to_string(), versions.to_vec());
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use crate::config::env::user_env_var;
+use crate::error::AppError;
+use crate::types::DefaultPackagesFile;
+
+/// Plugins known to read a default-packages file at install time:
+/// (plugin, env var overriding the path, default file in home).
+const FILES: &[(&str, &str, &str)] = &[
+    (
+        "nodejs",
+        "ASDF_NPM_DEFAULT_PACKAGES_FILE",
+        ".default-npm-packages",
+    ),
+    (
+        "python",
+        "ASDF_PYTHON_DEFAULT_PACKAGES_FILE",
+        ".default-python-packages",
+    ),
+    ("ruby", "ASDF_GEM_DEFAULT_PACKAGES_FILE", ".default-gems"),
+    (
+        "golang",
+        "ASDF_GOLANG_DEFAULT_PACKAGES_FILE",
+        ".default-golang-pkgs",
+    ),
+    (
+        "rust",
+        "ASDF_CRATE_DEFAULT_PACKAGES_FILE",
+        ".default-cargo-crates",
+    ),
+];
+
+pub fn supports(plugin: &str) -> bool {
+    FILES.iter().any(|(p, _, _)| *p == plugin)
+}
+
+/// Where `plugin` reads its default packages from, if it supports them.
+pub fn path_for(plugin: &str) -> Option<PathBuf> {
+    let (_, env, file) = FILES.iter().find(|(p, _, _)| *p == plugin)?;
+    user_env_var(env)
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(file)))
+}
+
+pub fn read(plugin: &str) -> Result<DefaultPackagesFile, AppError> {
+    let path = path_for(plugin).ok_or_else(|| {
+        AppError::ConfigError(format!("plugin {plugin} has no default-packages file"))
+    })?;
+    let content = std::fs::read_to_string(&path).ok();
+    Ok(DefaultPackagesFile {
+        plugin: plugin.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists: content.is_some(),
+        packages: content.as_deref().map(parse_packages).unwrap_or_default(),
+    })
+}
+
+/// Write `packages` to `plugin`'s file, keeping its comments.
+pub fn write(plugin: &str, packages: &[String]) -> Result<DefaultPackagesFile, AppError> {
+    if let Some(bad) = packages
+        .iter()
+        .find(|p| p.trim().is_empty() || p.contains('#') || p.chars().any(char::is_control))
+    {
+        return Err(AppError::ParseError(format!(
+            "invalid package entry {bad:?}"
+        )));
+    }
+    let file = read(plugin)?;
+    let content = std::fs::read_to_string(&file.path).unwrap_or_default();
+    std::fs::write(&file.path, update_packages(&content, packages))?;
+    read(plugin)
+}
+
+fn package_of(line: &str) -> Option<&str> {
+    let package = line.split('#').next().unwrap_or("").trim();
+    (!package.is_empty()).then_some(package)
+}
+
+pub fn parse_packages(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(package_of)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rewrite `content` to list exactly `packages`: comment and blank lines
+/// stay, kept packages keep their line (and trailing comment), removed ones
+/// are dropped and new ones are appended.
+pub fn update_packages(content: &str, packages: &[String]) -> String {
+    let mut out = String::new();
+    let mut kept: Vec<&str> = Vec::new();
+    for line in content.lines() {
+        match package_of(line) {
+            Some(package) if !packages.iter().any(|p| p == package) => continue,
+            Some(package) => kept.push(package),
+            None => {}
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    for package in packages {
+        if !kept.contains(&package.as_str()) {
+            out.push_str(package);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_packages_keeps_comments() {
+        let content = "# globals\ntypescript\neslint # linting\n\nprettier\n";
+        assert_eq!(
+            parse_packages(content),
+            vec!["typescript", "eslint", "prettier"]
+        );
+        let updated = update_packages(
+            content,
+            &[
+                "eslint".to_string(),
+                "pnpm".to_string(),
+                "typescript".to_string(),
+            ],
+        );
+        assert_eq!(updated, "# globals\ntypescript\neslint # linting\n\npnpm\n");
+    }
+}
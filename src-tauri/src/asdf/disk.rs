@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::asdf::versioning::{compare_versions, sort_versions};
+use crate::error::AppError;
+use crate::types::{DiskUsage, DownloadEntry, PluginUsage, VersionUsage};
+
+/// Size and newest modification time of a directory tree.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TreeStats {
+    pub bytes: u64,
+    pub modified: Option<u64>,
+}
+
+/// Sum file sizes under `path` without following symlinks. Unreadable
+/// entries are skipped.
+pub fn tree_stats(path: &Path) -> TreeStats {
+    let mut stats = TreeStats::default();
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        let Ok(meta) = std::fs::symlink_metadata(&current) else {
+            continue;
+        };
+        stats.modified = stats.modified.max(meta.modified().ok().and_then(unix_secs));
+        if meta.is_dir() {
+            if let Ok(entries) = std::fs::read_dir(&current) {
+                stack.extend(entries.flatten().map(|e| e.path()));
+            }
+        } else {
+            stats.bytes += meta.len();
+        }
+    }
+    stats
+}
+
+/// `<root>/<plugin>/<version>` stats, keyed by plugin then version. Each
+/// plugin is walked on its own thread.
+fn nested_stats(root: &Path) -> BTreeMap<String, BTreeMap<String, TreeStats>> {
+    std::thread::scope(|s| {
+        let walks: Vec<_> = child_dirs(root)
+            .into_iter()
+            .map(|(plugin, dir)| {
+                let walk = s.spawn(move || {
+                    child_dirs(&dir)
+                        .into_iter()
+                        .map(|(version, path)| (version, tree_stats(&path)))
+                        .collect::<BTreeMap<_, _>>()
+                });
+                (plugin, walk)
+            })
+            .collect();
+        walks
+            .into_iter()
+            .map(|(plugin, walk)| (plugin, walk.join().unwrap_or_default()))
+            .collect()
+    })
+}
+
+fn child_dirs(root: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        .collect()
+}
+
+/// Newest access time among an install's `bin` entries. Filesystems
+/// mounted `noatime` never update it, so this is only a hint.
+fn last_used(install: &Path) -> Option<u64> {
+    std::fs::read_dir(install.join("bin"))
+        .ok()?
+        .flatten()
+        .filter_map(|e| e.metadata().ok()?.accessed().ok())
+        .filter_map(unix_secs)
+        .max()
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Area under the data dir where an install is kept while it is being
+/// reinstalled, outside `installs` so asdf does not list it.
+pub const REINSTALL_BACKUPS: &str = ".reinstall-backup";
+
+/// Directory asdf installs a pinned version into: `ref:<rev>` pins live
+/// in `ref-<rev>`.
+pub fn install_dir_name(version: &str) -> String {
+    match version.strip_prefix("ref:") {
+        Some(rev) => format!("ref-{rev}"),
+        None => version.to_string(),
+    }
+}
+
+/// The version asdf expects for an install directory, the inverse of
+/// [`install_dir_name`].
+pub fn version_for_dir(dir_name: &str) -> String {
+    match dir_name.strip_prefix("ref-") {
+        Some(rev) => format!("ref:{rev}"),
+        None => dir_name.to_string(),
+    }
+}
+
+/// Kept downloads under `data_dir/downloads`, per plugin and then oldest
+/// to newest version.
+pub fn list_downloads(data_dir: &Path) -> Vec<DownloadEntry> {
+    let installs = data_dir.join("installs");
+    let mut entries: Vec<DownloadEntry> = nested_stats(&data_dir.join("downloads"))
+        .into_iter()
+        .flat_map(|(tool, versions)| {
+            let installs = installs.join(&tool);
+            versions
+                .into_iter()
+                .map(move |(version, stats)| DownloadEntry {
+                    installed: installs.join(&version).is_dir(),
+                    tool: tool.clone(),
+                    version,
+                    bytes: stats.bytes,
+                    modified: stats.modified,
+                })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.tool
+            .cmp(&b.tool)
+            .then_with(|| compare_versions(&a.version, &b.version))
+    });
+    entries
+}
+
+/// `data_dir/<area>/<tool>/<version>` for `installs` or `downloads`,
+/// refusing names that would step outside it.
+pub fn version_dir(
+    data_dir: &Path,
+    area: &str,
+    tool: &str,
+    version: &str,
+) -> Result<PathBuf, AppError> {
+    for part in [tool, version] {
+        let mut components = Path::new(part).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            log::warn!("refused {area} path component {part:?}");
+            return Err(AppError::PathNotAllowed(format!(
+                "invalid {area} name {part:?}"
+            )));
+        }
+    }
+    Ok(data_dir.join(area).join(tool).join(version))
+}
+
+/// Delete one kept download, returning the bytes freed.
+pub fn remove_download(data_dir: &Path, tool: &str, version: &str) -> Result<u64, AppError> {
+    let dir = version_dir(data_dir, "downloads", tool, version)?;
+    let bytes = tree_stats(&dir).bytes;
+    std::fs::remove_dir_all(&dir)?;
+    Ok(bytes)
+}
+
+/// Walk `installs`, `downloads` and `plugins` under `data_dir`, one thread
+/// per tree.
+pub fn disk_usage(data_dir: &Path) -> DiskUsage {
+    let installs_dir = data_dir.join("installs");
+    let (installs, downloads, plugins) = std::thread::scope(|s| {
+        let installs = s.spawn(|| nested_stats(&installs_dir));
+        let downloads = s.spawn(|| nested_stats(&data_dir.join("downloads")));
+        let plugins = s.spawn(|| {
+            child_dirs(&data_dir.join("plugins"))
+                .into_iter()
+                .map(|(name, path)| (name, tree_stats(&path).bytes))
+                .collect::<BTreeMap<_, _>>()
+        });
+        (
+            installs.join().unwrap_or_default(),
+            downloads.join().unwrap_or_default(),
+            plugins.join().unwrap_or_default(),
+        )
+    });
+
+    let mut names: Vec<&String> = installs
+        .keys()
+        .chain(downloads.keys())
+        .chain(plugins.keys())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let empty = BTreeMap::new();
+    let usage: Vec<PluginUsage> = names
+        .into_iter()
+        .map(|name| {
+            let installed = installs.get(name).unwrap_or(&empty);
+            let downloaded = downloads.get(name).unwrap_or(&empty);
+            let mut versions: Vec<String> =
+                installed.keys().chain(downloaded.keys()).cloned().collect();
+            sort_versions(&mut versions);
+            versions.dedup();
+            let versions: Vec<VersionUsage> = versions
+                .iter()
+                .map(|version| {
+                    let install = installed.get(version).copied().unwrap_or_default();
+                    VersionUsage {
+                        version: version.clone(),
+                        installed: installed.contains_key(version),
+                        install_bytes: install.bytes,
+                        download_bytes: downloaded.get(version).map_or(0, |s| s.bytes),
+                        modified: install.modified,
+                        last_used: installed
+                            .contains_key(version)
+                            .then(|| last_used(&installs_dir.join(name).join(version)))
+                            .flatten(),
+                    }
+                })
+                .collect();
+            PluginUsage {
+                name: name.clone(),
+                plugin_bytes: plugins.get(name).copied().unwrap_or(0),
+                install_bytes: versions.iter().map(|v| v.install_bytes).sum(),
+                download_bytes: versions.iter().map(|v| v.download_bytes).sum(),
+                versions,
+            }
+        })
+        .collect();
+
+    let installs_bytes = usage.iter().map(|p| p.install_bytes).sum();
+    let downloads_bytes = usage.iter().map(|p| p.download_bytes).sum();
+    let plugins_bytes = usage.iter().map(|p| p.plugin_bytes).sum();
+    DiskUsage {
+        data_dir: data_dir.to_string_lossy().to_string(),
+        installs_bytes,
+        downloads_bytes,
+        plugins_bytes,
+        total_bytes: installs_bytes + downloads_bytes + plugins_bytes,
+        plugins: usage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_usage() {
+        let dir = std::env::temp_dir().join(format!("asdf-gui-disk-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let install = dir.join("installs/nodejs/20.11.0/bin");
+        std::fs::create_dir_all(&install).unwrap();
+        std::fs::write(install.join("node"), [0u8; 100]).unwrap();
+        std::fs::create_dir_all(dir.join("downloads/nodejs/18.19.0")).unwrap();
+        std::fs::write(dir.join("downloads/nodejs/18.19.0/src.tar"), [0u8; 40]).unwrap();
+        std::fs::create_dir_all(dir.join("plugins/nodejs")).unwrap();
+        std::fs::write(dir.join("plugins/nodejs/README"), [0u8; 7]).unwrap();
+        std::fs::create_dir_all(dir.join("installs/nodejs/9.11.2")).unwrap();
+
+        let usage = disk_usage(&dir);
+        assert_eq!(usage.total_bytes, 147);
+        let node = &usage.plugins[0];
+        assert_eq!(node.plugin_bytes, 7);
+        let versions: Vec<&str> = node.versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(versions, vec!["9.11.2", "18.19.0", "20.11.0"]);
+        assert_eq!(node.versions[1].download_bytes, 40);
+        assert!(!node.versions[1].installed);
+        assert_eq!(node.versions[2].install_bytes, 100);
+        assert!(node.versions[2].modified.is_some());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_version_dir_stays_inside() {
+        let data_dir = Path::new("/home/user/.asdf");
+        assert_eq!(
+            version_dir(data_dir, "downloads", "nodejs", "20.11.0").unwrap(),
+            data_dir.join("downloads/nodejs/20.11.0")
+        );
+        assert!(version_dir(data_dir, "downloads", "nodejs", "..").is_err());
+        assert!(version_dir(data_dir, "downloads", "..", "x").is_err());
+        assert!(version_dir(data_dir, "downloads", "nodejs", "20/../../..").is_err());
+        assert!(version_dir(data_dir, "downloads", "nodejs", "/etc").is_err());
+    }
+}
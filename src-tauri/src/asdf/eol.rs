@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use crate::asdf::releases::{self, ReleaseData};
+use crate::error::AppError;
+use crate::types::{EolEntry, EolReport, EolStatus, ReportFormat, ToolVersionsFile};
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (y, m, d) = (parts.next()??, parts.next()??, parts.next()??);
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    // Howard Hinnant's days_from_civil
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+/// `YYYY-MM-DD` for days since 1970-01-01.
+pub fn format_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Pinned versions in `files` that are past end-of-life on `today`, or
+/// reach it within `within_days`. Sorted by days left, soonest first.
+pub fn eol_report(
+    files: &[ToolVersionsFile],
+    data: &ReleaseData,
+    today: i64,
+    within_days: u32,
+) -> EolReport {
+    let mut pins: BTreeMap<(&str, &str), Vec<String>> = BTreeMap::new();
+    for file in files {
+        for entry in &file.entries {
+            for version in &entry.versions {
+                pins.entry((entry.tool.as_str(), version.as_str()))
+                    .or_default()
+                    .push(file.path.clone());
+            }
+        }
+    }
+
+    let mut entries: Vec<EolEntry> = pins
+        .into_iter()
+        .filter_map(|((tool, version), files)| {
+            let info = releases::lookup(data, tool, version)?;
+            let eol = info.eol.as_deref()?;
+            let days_left = parse_date(eol)? - today;
+            let status = if days_left < 0 {
+                EolStatus::Expired
+            } else if days_left <= i64::from(within_days) {
+                EolStatus::ExpiringSoon
+            } else {
+                return None;
+            };
+            Some(EolEntry {
+                tool: tool.to_string(),
+                version: version.to_string(),
+                cycle: info.cycle.clone(),
+                eol: eol.to_string(),
+                days_left,
+                status,
+                files,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|e| e.days_left);
+
+    EolReport {
+        date: format_date(today),
+        within_days,
+        files: files.iter().map(|f| f.path.clone()).collect(),
+        entries,
+    }
+}
+
+/// Render a report for sharing.
+pub fn render(report: &EolReport, format: &ReportFormat) -> Result<String, AppError> {
+    match format {
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(report).map_err(|e| AppError::ParseError(e.to_string()))
+        }
+        ReportFormat::Markdown => Ok(render_markdown(report)),
+    }
+}
+
+fn render_markdown(report: &EolReport) -> String {
+    let mut out = format!(
+        "# End-of-life report\n\nGenerated {} across {} version files, warning {} days ahead.\n\n",
+        report.date,
+        report.files.len(),
+        report.within_days
+    );
+    if report.entries.is_empty() {
+        out.push_str("No pinned runtimes are past or near end-of-life.\n");
+        return out;
+    }
+    out.push_str("| Tool | Version | Cycle | EOL | Status | Files |\n");
+    out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+    for e in &report.entries {
+        let status = match e.status {
+            EolStatus::Expired => format!("expired {} days ago", -e.days_left),
+            EolStatus::ExpiringSoon => format!("{} days left", e.days_left),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            e.tool,
+            e.version,
+            e.cycle,
+            e.eol,
+            status,
+            e.files.join("<br>")
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ReleaseInfo, ToolVersion};
+
+    #[test]
+    fn test_dates_round_trip() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2024-03-01"), Some(19_783));
+        assert_eq!(format_date(19_783), "2024-03-01");
+        assert_eq!(parse_date("2024-13-01"), None);
+    }
+
+    #[test]
+    fn test_eol_report() {
+        let info = |cycle: &str, eol: &str| ReleaseInfo {
+            cycle: cycle.to_string(),
+            lts: true,
+            release_date: None,
+            eol: Some(eol.to_string()),
+        };
+        let data: ReleaseData = [(
+            "nodejs".to_string(),
+            vec![
+                info("16", "2023-09-11"),
+                info("18", "2025-04-30"),
+                info("20", "2026-04-30"),
+            ],
+        )]
+        .into();
+        let files = vec![ToolVersionsFile {
+            path: "/work/app/.tool-versions".to_string(),
+            entries: vec![ToolVersion {
+                tool: "nodejs".to_string(),
+                versions: vec!["20.11.0".into(), "18.19.0".into(), "16.20.2".into()],
+            }],
+        }];
+        let today = parse_date("2025-04-01").unwrap();
+        let report = eol_report(&files, &data, today, 60);
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.entries[0].version, "16.20.2");
+        assert_eq!(report.entries[0].status, EolStatus::Expired);
+        assert_eq!(report.entries[1].days_left, 29);
+        assert!(
+            render(&report, &ReportFormat::Markdown)
+                .unwrap()
+                .contains("| nodejs | 18.19.0 | 18 | 2025-04-30 | 29 days left |")
+        );
+    }
+}
@@ -1,9 +1,13 @@
-use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
+use crate::asdf::{args, shell_scope};
+use crate::config::app_config;
+use crate::config::env::get_user_path;
 use crate::error::AppError;
 use crate::types::InstallEvent;
 
@@ -16,40 +20,61 @@ pub struct CommandOutput {
     pub exit_code: i32,
 }
 
-/// Cache the user's login-shell PATH so we only resolve it once.
-static SHELL_PATH: OnceLock<String> = OnceLock::new();
-
-/// Get the user's full PATH by invoking their login shell.
-/// Tauri apps launched from Finder/Dock inherit a minimal PATH
-/// (/usr/bin:/bin:/usr/sbin:/sbin), so we need the real one.
-fn get_user_path() -> &'static str {
-    SHELL_PATH.get_or_init(|| {
-        // Try the user's SHELL, fallback to /bin/zsh (macOS default), then /bin/bash
-        let shells = [
-            std::env::var("SHELL").unwrap_or_default(),
-            "/bin/zsh".to_string(),
-            "/bin/bash".to_string(),
-        ];
-
-        for shell in &shells {
-            if shell.is_empty() {
-                continue;
-            }
-            if let Ok(output) = std::process::Command::new(shell)
-                .args(["-lc", "echo $PATH"])
-                .output()
-                && output.status.success()
-            {
-                let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !path.is_empty() {
-                    return path;
-                }
-            }
-        }
+/// Run a filesystem walk on the blocking pool so large trees do not stall
+/// the async runtime.
+pub async fn blocking<T, F>(walk: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::task::spawn_blocking(walk)
+        .await
+        .map_err(|e| AppError::ProcessError(e.to_string()))
+}
 
-        // Last resort: return the current (minimal) PATH
-        std::env::var("PATH").unwrap_or_default()
-    })
+/// Environment every asdf child process gets: the login PATH, the
+/// resolved version file name so asdf reads and writes the same file the
+/// app does, and any Shell-scope overrides for `cwd`.
+fn apply_user_env(cmd: &mut Command, cwd: Option<&str>) {
+    cmd.env("PATH", get_user_path());
+    cmd.env(
+        "ASDF_DEFAULT_TOOL_VERSIONS_FILENAME",
+        app_config::tool_versions_filename(),
+    );
+    if let Some(dir) = cwd {
+        cmd.envs(shell_scope::env_for(dir));
+    }
+}
+
+/// Per-binary answer to `args::accepts_separator`, asked once.
+static SEPARATOR_SUPPORT: OnceLock<Mutex<HashMap<PathBuf, bool>>> = OnceLock::new();
+
+fn accepts_separator(binary: &Path) -> bool {
+    let cache = SEPARATOR_SUPPORT.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(known) = cache.lock().ok().and_then(|c| c.get(binary).copied()) {
+        return known;
+    }
+    let supported = std::process::Command::new(binary)
+        .arg("version")
+        .env("PATH", get_user_path())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .is_some_and(|o| args::accepts_separator(&String::from_utf8_lossy(&o.stdout)));
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(binary.to_path_buf(), supported);
+    }
+    supported
+}
+
+/// `args` as `binary` should receive them: without the `--` separator if
+/// it would be read as a positional.
+fn binary_args<'a>(binary: &Path, args: &[&'a str]) -> Vec<&'a str> {
+    if args.contains(&"--") && !accepts_separator(binary) {
+        args::drop_separator(args)
+    } else {
+        args.to_vec()
+    }
 }
 
 /// Well-known asdf installation directories to check as fallback.
@@ -99,7 +124,7 @@ pub fn resolve_asdf_binary(config_override: Option<&str>) -> Result<PathBuf, App
     let user_path = get_user_path();
     if let Ok(output) = std::process::Command::new("which")
         .arg("asdf")
-        .env("PATH", user_path)
+        .env("PATH", &user_path)
         .output()
         && output.status.success()
     {
@@ -138,8 +163,8 @@ pub async fn run_asdf(
 ) -> Result<CommandOutput, AppError> {
     let binary = resolve_asdf_binary(asdf_path)?;
     let mut cmd = Command::new(&binary);
-    cmd.args(args);
-    cmd.env("PATH", get_user_path());
+    cmd.args(binary_args(&binary, args));
+    apply_user_env(&mut cmd, cwd);
 
     if let Some(dir) = cwd {
         cmd.current_dir(dir);
@@ -169,19 +194,22 @@ pub async fn run_asdf(
 }
 
 /// Streaming variant for long-running commands (install, update).
-/// Sends each line of stdout/stderr to the frontend via Tauri Channel.
-pub async fn run_asdf_streaming(
+/// Sends each line of stdout/stderr to `on_event` as it arrives.
+pub async fn run_asdf_streaming<F>(
     args: &[&str],
     cwd: Option<&str>,
     asdf_path: Option<&str>,
-    channel: &tauri::ipc::Channel<InstallEvent>,
-) -> Result<(), AppError> {
+    on_event: F,
+) -> Result<(), AppError>
+where
+    F: Fn(InstallEvent) + Clone + Send + 'static,
+{
     use std::process::Stdio;
 
     let binary = resolve_asdf_binary(asdf_path)?;
     let mut cmd = Command::new(&binary);
-    cmd.args(args);
-    cmd.env("PATH", get_user_path());
+    cmd.args(binary_args(&binary, args));
+    apply_user_env(&mut cmd, cwd);
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
 
@@ -196,22 +224,22 @@ pub async fn run_asdf_streaming(
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
 
-    let channel_out = channel.clone();
+    let on_out = on_event.clone();
     let stdout_handle = tokio::spawn(async move {
         if let Some(stdout) = stdout {
             let mut reader = BufReader::new(stdout).lines();
             while let Ok(Some(line)) = reader.next_line().await {
-                let _ = channel_out.send(InstallEvent::Stdout(line));
+                on_out(InstallEvent::Stdout(line));
             }
         }
     });
 
-    let channel_err = channel.clone();
+    let on_err = on_event.clone();
     let stderr_handle = tokio::spawn(async move {
         if let Some(stderr) = stderr {
             let mut reader = BufReader::new(stderr).lines();
             while let Ok(Some(line)) = reader.next_line().await {
-                let _ = channel_err.send(InstallEvent::Stderr(line));
+                on_err(InstallEvent::Stderr(line));
             }
         }
     });
@@ -225,7 +253,7 @@ pub async fn run_asdf_streaming(
         .map_err(|e| AppError::ProcessError(e.to_string()))?;
 
     let success = status.success();
-    let _ = channel.send(InstallEvent::Finished { success });
+    on_event(InstallEvent::Finished { success });
 
     if !success {
         return Err(AppError::AsdfError {
@@ -236,3 +264,61 @@ pub async fn run_asdf_streaming(
 
     Ok(())
 }
+
+/// Run `git` against a local repository (plugin checkouts). Only local
+/// subcommands are meant to go through here; failures carry git's stderr.
+pub async fn run_git(args: &[&str], dir: &Path) -> Result<CommandOutput, AppError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("PATH", get_user_path())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .await
+        .map_err(|e| AppError::ProcessError(format!("git: {e}")))?;
+
+    let exit_code = output.status.code().unwrap_or(-1);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if exit_code != 0 {
+        return Err(AppError::ProcessError(format!(
+            "git {}: {}",
+            args.first().copied().unwrap_or_default(),
+            stderr.trim()
+        )));
+    }
+    Ok(CommandOutput {
+        stdout,
+        stderr,
+        exit_code,
+    })
+}
+
+/// Run a plugin callback such as `bin/list-legacy-filenames` with the same
+/// environment asdf gives it, so scripts relying on the login PATH work when
+/// the app is launched from a desktop session.
+pub async fn run_plugin_script(script: &Path) -> Result<CommandOutput, AppError> {
+    let mut cmd = Command::new(script);
+    apply_user_env(&mut cmd, None);
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| AppError::ProcessError(format!("{}: {e}", script.display())))?;
+
+    let exit_code = output.status.code().unwrap_or(-1);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if exit_code != 0 {
+        return Err(AppError::ProcessError(format!(
+            "{}: {}",
+            script.display(),
+            stderr.trim()
+        )));
+    }
+    Ok(CommandOutput {
+        stdout,
+        stderr,
+        exit_code,
+    })
+}
@@ -0,0 +1,345 @@
+use toml_edit::{Array, DocumentMut, Item, value};
+
+use crate::error::AppError;
+use crate::types::{ExportTarget, ToolVersion};
+
+/// File an export target writes, relative to the `.tool-versions` directory.
+pub fn file_name(target: &ExportTarget) -> &'static str {
+    match target {
+        ExportTarget::Nvmrc => ".nvmrc",
+        ExportTarget::PythonVersion => ".python-version",
+        ExportTarget::RustToolchain => "rust-toolchain.toml",
+        ExportTarget::MiseToml => "mise.toml",
+        ExportTarget::GoMod => "go.mod",
+    }
+}
+
+/// New content for a target file, plus every place the existing file
+/// disagrees with `.tool-versions`.
+#[derive(Debug)]
+pub struct Rendered {
+    pub content: String,
+    pub conflicts: Vec<String>,
+}
+
+/// Render `target` from parsed `.tool-versions` entries, updating `existing`
+/// in place where the format allows. Returns `None` when the target does not
+/// apply (the tool is not pinned, or there is no `go.mod` to update).
+pub fn render(
+    target: &ExportTarget,
+    entries: &[ToolVersion],
+    existing: Option<&str>,
+) -> Result<Option<Rendered>, AppError> {
+    match target {
+        ExportTarget::Nvmrc => Ok(render_nvmrc(entries, existing)),
+        ExportTarget::PythonVersion => Ok(render_python_version(entries, existing)),
+        ExportTarget::RustToolchain => render_rust_toolchain(entries, existing),
+        ExportTarget::MiseToml => render_mise_toml(entries, existing).map(Some),
+        ExportTarget::GoMod => Ok(render_go_mod(entries, existing)),
+    }
+}
+
+/// Versions pinned for the first tool matching one of `names`, skipping
+/// `ref:`/`path:` pins that single-version files cannot express.
+fn pinned<'a>(entries: &'a [ToolVersion], names: &[&str]) -> Option<&'a [String]> {
+    entries
+        .iter()
+        .find(|e| names.contains(&e.tool.as_str()))
+        .map(|e| e.versions.as_slice())
+        .filter(|vs| {
+            vs.first()
+                .is_some_and(|v| !v.starts_with("ref:") && !v.starts_with("path:"))
+        })
+}
+
+fn render_nvmrc(entries: &[ToolVersion], existing: Option<&str>) -> Option<Rendered> {
+    let version = pinned(entries, &["nodejs", "node"])?.first()?;
+    let mut conflicts = Vec::new();
+    if let Some(existing) = existing {
+        let current = existing.trim().trim_start_matches('v');
+        if current == version {
+            return Some(Rendered {
+                content: existing.to_string(),
+                conflicts,
+            });
+        }
+        if !current.is_empty() {
+            conflicts.push(format!(
+                "nodejs: .nvmrc has {current}, .tool-versions has {version}"
+            ));
+        }
+    }
+    Some(Rendered {
+        content: format!("{version}\n"),
+        conflicts,
+    })
+}
+
+fn render_python_version(entries: &[ToolVersion], existing: Option<&str>) -> Option<Rendered> {
+    let versions = pinned(entries, &["python"])?;
+    let mut conflicts = Vec::new();
+    if let Some(existing) = existing {
+        let current: Vec<&str> = existing
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect();
+        if current == versions {
+            return Some(Rendered {
+                content: existing.to_string(),
+                conflicts,
+            });
+        }
+        if !current.is_empty() {
+            conflicts.push(format!(
+                "python: .python-version has {}, .tool-versions has {}",
+                current.join(" "),
+                versions.join(" ")
+            ));
+        }
+    }
+    Some(Rendered {
+        content: format!("{}\n", versions.join("\n")),
+        conflicts,
+    })
+}
+
+fn render_rust_toolchain(
+    entries: &[ToolVersion],
+    existing: Option<&str>,
+) -> Result<Option<Rendered>, AppError> {
+    let Some(version) = pinned(entries, &["rust"]).and_then(|vs| vs.first()) else {
+        return Ok(None);
+    };
+    let mut doc = parse_toml(existing.unwrap_or(""), "rust-toolchain.toml")?;
+    let mut conflicts = Vec::new();
+    let current = doc
+        .get("toolchain")
+        .and_then(|t| t.get("channel"))
+        .and_then(|c| c.as_str())
+        .map(str::to_string);
+    match current {
+        Some(ref c) if c == version => {}
+        _ => {
+            if let Some(c) = current {
+                conflicts.push(format!(
+                    "rust: rust-toolchain.toml has {c}, .tool-versions has {version}"
+                ));
+            }
+            if !doc.contains_table("toolchain") {
+                doc["toolchain"] = toml_edit::table();
+            }
+            doc["toolchain"]["channel"] = value(version.as_str());
+        }
+    }
+    Ok(Some(Rendered {
+        content: doc.to_string(),
+        conflicts,
+    }))
+}
+
+fn render_mise_toml(entries: &[ToolVersion], existing: Option<&str>) -> Result<Rendered, AppError> {
+    let mut doc = parse_toml(existing.unwrap_or(""), "mise.toml")?;
+    // `[tools]` may also be written inline as `tools = { ... }`.
+    if !doc.get("tools").is_some_and(Item::is_table_like) {
+        doc["tools"] = toml_edit::table();
+    }
+    let tools = doc["tools"]
+        .as_table_like_mut()
+        .ok_or_else(|| AppError::ParseError("mise.toml: tools is not a table".to_string()))?;
+    let mut conflicts = Vec::new();
+    for entry in entries.iter().filter(|e| !e.versions.is_empty()) {
+        let current = tools.get(&entry.tool).map(mise_versions);
+        if current.as_deref() == Some(entry.versions.as_slice()) {
+            continue;
+        }
+        if let Some(current) = current {
+            conflicts.push(format!(
+                "{}: mise.toml has {}, .tool-versions has {}",
+                entry.tool,
+                current.join(" "),
+                entry.versions.join(" ")
+            ));
+        }
+        let versions = if entry.versions.len() == 1 {
+            value(entry.versions[0].as_str())
+        } else {
+            value(entry.versions.iter().collect::<Array>())
+        };
+        // Table-form entries keep their other keys, such as `postinstall`.
+        match tools.get_mut(&entry.tool).and_then(Item::as_table_like_mut) {
+            Some(options) => {
+                options.insert("version", versions);
+            }
+            None => {
+                tools.insert(&entry.tool, versions);
+            }
+        }
+    }
+    Ok(Rendered {
+        content: doc.to_string(),
+        conflicts,
+    })
+}
+
+/// Versions from a mise `[tools]` value: `"20"`, `["20", "18"]`
+/// or `{ version = "20" }`.
+fn mise_versions(item: &Item) -> Vec<String> {
+    if let Some(s) = item.as_str() {
+        return vec![s.to_string()];
+    }
+    if let Some(array) = item.as_array() {
+        return array
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect();
+    }
+    item.get("version")
+        .and_then(|v| v.as_str())
+        .map(|s| vec![s.to_string()])
+        .unwrap_or_default()
+}
+
+fn render_go_mod(entries: &[ToolVersion], existing: Option<&str>) -> Option<Rendered> {
+    // A go.mod needs a module path we cannot invent; only update existing ones.
+    let existing = existing?;
+    let version = pinned(entries, &["golang", "go"])?.first()?;
+    let mut conflicts = Vec::new();
+    let mut found = false;
+    let mut lines: Vec<String> = Vec::new();
+    for line in existing.lines() {
+        match line.trim().strip_prefix("go ") {
+            Some(current) if !found => {
+                found = true;
+                let current = current.trim();
+                if current != version {
+                    conflicts.push(format!(
+                        "golang: go.mod has {current}, .tool-versions has {version}"
+                    ));
+                }
+                lines.push(format!("go {version}"));
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    if !found {
+        // The go directive conventionally follows the module line.
+        let at = lines
+            .iter()
+            .position(|l| l.trim_start().starts_with("module "))
+            .map_or(0, |i| i + 1);
+        lines.insert(at, String::new());
+        lines.insert(at + 1, format!("go {version}"));
+    }
+    if conflicts.is_empty() && found {
+        return Some(Rendered {
+            content: existing.to_string(),
+            conflicts,
+        });
+    }
+    Some(Rendered {
+        content: format!("{}\n", lines.join("\n")),
+        conflicts,
+    })
+}
+
+fn parse_toml(content: &str, name: &str) -> Result<DocumentMut, AppError> {
+    content
+        .parse::<DocumentMut>()
+        .map_err(|e| AppError::ParseError(format!("{name}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tv(tool: &str, versions: &[&str]) -> ToolVersion {
+        ToolVersion {
+            tool: tool.to_string(),
+            versions: versions.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_nvmrc_conflict() {
+        let entries = vec![tv("nodejs", &["20.11.0"])];
+        let result = render(&ExportTarget::Nvmrc, &entries, Some("v18.19.0\n"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.content, "20.11.0\n");
+        assert_eq!(result.conflicts.len(), 1);
+
+        let result = render(&ExportTarget::Nvmrc, &entries, Some("v20.11.0\n"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.content, "v20.11.0\n");
+        assert!(result.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_render_rust_toolchain_keeps_other_keys() {
+        let entries = vec![tv("rust", &["1.75.0"])];
+        let existing = "[toolchain]\nchannel = \"1.74.0\"\ncomponents = [\"clippy\"]\n";
+        let result = render(&ExportTarget::RustToolchain, &entries, Some(existing))
+            .unwrap()
+            .unwrap();
+        assert!(result.content.contains("channel = \"1.75.0\""));
+        assert!(result.content.contains("components = [\"clippy\"]"));
+        assert_eq!(result.conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_render_mise_toml() {
+        let entries = vec![
+            tv("nodejs", &["20.11.0"]),
+            tv("python", &["3.12.1", "3.11.7"]),
+        ];
+        let result = render(&ExportTarget::MiseToml, &entries, None)
+            .unwrap()
+            .unwrap();
+        assert!(result.content.contains("[tools]"));
+        assert!(result.content.contains("nodejs = \"20.11.0\""));
+        assert!(result.content.contains("python = [\"3.12.1\", \"3.11.7\"]"));
+        assert!(result.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_render_mise_toml_keeps_inline_tools_and_options() {
+        let entries = vec![tv("nodejs", &["20.11.0"])];
+        let existing = "tools = { python = \"3.12\", nodejs = \"18\" }\n";
+        let result = render(&ExportTarget::MiseToml, &entries, Some(existing))
+            .unwrap()
+            .unwrap();
+        assert!(result.content.contains("python = \"3.12\""));
+        assert!(result.content.contains("nodejs = \"20.11.0\""));
+        assert!(!result.content.contains("[tools]"));
+        assert_eq!(result.conflicts.len(), 1);
+
+        let existing =
+            "[tools]\nnodejs = { version = \"18\", postinstall = \"corepack enable\" }\n";
+        let result = render(&ExportTarget::MiseToml, &entries, Some(existing))
+            .unwrap()
+            .unwrap();
+        assert!(result.content.contains("version = \"20.11.0\""));
+        assert!(result.content.contains("postinstall = \"corepack enable\""));
+        assert_eq!(result.conflicts.len(), 1);
+    }
+
+    #[test]
+    fn test_render_go_mod() {
+        let entries = vec![tv("golang", &["1.22.0"])];
+        assert!(
+            render(&ExportTarget::GoMod, &entries, None)
+                .unwrap()
+                .is_none()
+        );
+
+        let existing = "module example.com/app\n\ngo 1.21\n\nrequire foo v1.0.0\n";
+        let result = render(&ExportTarget::GoMod, &entries, Some(existing))
+            .unwrap()
+            .unwrap();
+        assert!(result.content.contains("go 1.22.0\n"));
+        assert!(result.content.contains("require foo v1.0.0"));
+        assert_eq!(result.conflicts.len(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+
+use crate::asdf::disk::{install_dir_name, version_for_dir};
+use crate::types::{DiskUsage, GcCandidate, GcPlan, ToolVersionsFile};
+
+/// Installed versions pinned by none of `files`, largest first.
+pub fn gc_plan(usage: &DiskUsage, files: &[ToolVersionsFile]) -> GcPlan {
+    let pinned: HashSet<(&str, String)> = files
+        .iter()
+        .flat_map(|f| &f.entries)
+        .flat_map(|e| {
+            e.versions
+                .iter()
+                .map(|v| (e.tool.as_str(), install_dir_name(v)))
+        })
+        .collect();
+
+    let mut candidates: Vec<GcCandidate> = usage
+        .plugins
+        .iter()
+        .flat_map(|p| p.versions.iter().map(move |v| (p, v)))
+        .filter(|(p, v)| v.installed && !pinned.contains(&(p.name.as_str(), v.version.clone())))
+        .map(|(p, v)| GcCandidate {
+            tool: p.name.clone(),
+            version: version_for_dir(&v.version),
+            bytes: v.install_bytes + v.download_bytes,
+            last_used: v.last_used,
+        })
+        .collect();
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.bytes));
+
+    GcPlan {
+        files: files.iter().map(|f| f.path.clone()).collect(),
+        total_bytes: candidates.iter().map(|c| c.bytes).sum(),
+        candidates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PluginUsage, ToolVersion, VersionUsage};
+
+    fn version(name: &str, installed: bool, bytes: u64) -> VersionUsage {
+        VersionUsage {
+            version: name.to_string(),
+            installed,
+            install_bytes: bytes,
+            download_bytes: 0,
+            modified: None,
+            last_used: None,
+        }
+    }
+
+    #[test]
+    fn test_gc_plan_keeps_pinned_versions() {
+        let usage = DiskUsage {
+            data_dir: "/home/user/.asdf".to_string(),
+            installs_bytes: 0,
+            downloads_bytes: 0,
+            plugins_bytes: 0,
+            total_bytes: 0,
+            plugins: vec![PluginUsage {
+                name: "nodejs".to_string(),
+                plugin_bytes: 0,
+                install_bytes: 0,
+                download_bytes: 0,
+                versions: vec![
+                    version("18.19.0", true, 10),
+                    version("20.11.0", true, 20),
+                    version("21.0.0", false, 5),
+                    version("ref-abc123", true, 30),
+                    version("16.20.0", true, 40),
+                    version("ref-def456", true, 15),
+                ],
+            }],
+        };
+        let files = vec![ToolVersionsFile {
+            path: "/home/user/.tool-versions".to_string(),
+            entries: vec![ToolVersion {
+                tool: "nodejs".to_string(),
+                versions: vec!["20.11.0".to_string(), "ref:abc123".to_string()],
+            }],
+        }];
+        let plan = gc_plan(&usage, &files);
+        let versions: Vec<&str> = plan.candidates.iter().map(|c| c.version.as_str()).collect();
+        assert_eq!(versions, vec!["16.20.0", "ref:def456", "18.19.0"]);
+        assert_eq!(plan.total_bytes, 65);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::asdf::disk::install_dir_name;
+use crate::error::AppError;
+use crate::types::{ToolVersionsFile, UninstallImpact};
+
+/// Whether `pin` (as written in a version file) selects `version`. Either
+/// side may be a `ref:<rev>` pin or its `ref-<rev>` install directory.
+fn selects(pin: &str, version: &str) -> bool {
+    install_dir_name(pin) == install_dir_name(version)
+}
+
+/// Whether the `tool` entry of `file` lists `version` first.
+pub fn pins_first(file: &ToolVersionsFile, tool: &str, version: &str) -> bool {
+    file.entries
+        .iter()
+        .find(|e| e.tool == tool)
+        .and_then(|e| e.versions.first())
+        .is_some_and(|v| selects(v, version))
+}
+
+/// Refuse to touch `tool` `version` while `home` selects it globally.
+pub fn ensure_not_global(
+    home: Option<&ToolVersionsFile>,
+    tool: &str,
+    version: &str,
+) -> Result<(), AppError> {
+    match home {
+        Some(home) if pins_first(home, tool, version) => Err(AppError::InUse(format!(
+            "{tool} {version} is the global version in {}",
+            home.path
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Everything known to reference `tool` `version`. `env` holds the
+/// candidate `(name, value)` variables to check.
+pub fn uninstall_impact(
+    tool: &str,
+    version: &str,
+    files: &[ToolVersionsFile],
+    home_file: Option<&ToolVersionsFile>,
+    env: &[(String, String)],
+) -> UninstallImpact {
+    let references = files
+        .iter()
+        .filter(|f| {
+            f.entries
+                .iter()
+                .filter(|e| e.tool == tool)
+                .any(|e| e.versions.iter().any(|v| selects(v, version)))
+        })
+        .map(|f| f.path.clone())
+        .collect();
+    let env_vars = env
+        .iter()
+        .filter(|(_, value)| value.split_whitespace().any(|v| selects(v, version)))
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    UninstallImpact {
+        tool: tool.to_string(),
+        version: version.to_string(),
+        files: references,
+        env_vars,
+        is_global: home_file.is_some_and(|f| pins_first(f, tool, version)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ToolVersion;
+
+    fn file(path: &str, versions: &[&str]) -> ToolVersionsFile {
+        ToolVersionsFile {
+            path: path.to_string(),
+            entries: vec![ToolVersion {
+                tool: "nodejs".to_string(),
+                versions: versions.iter().map(|s| s.to_string()).collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_uninstall_impact() {
+        let home = file("/home/user/.tool-versions", &["20.11.0", "18.19.0"]);
+        let project = file("/work/app/.tool-versions", &["18.19.0"]);
+        let files = vec![home.clone(), project];
+        let env = vec![("ASDF_NODEJS_VERSION".to_string(), "18.19.0".to_string())];
+
+        let impact = uninstall_impact("nodejs", "18.19.0", &files, Some(&home), &env);
+        assert_eq!(impact.files.len(), 2);
+        assert_eq!(impact.env_vars, vec!["ASDF_NODEJS_VERSION=18.19.0"]);
+        assert!(!impact.is_global);
+
+        let impact = uninstall_impact("nodejs", "20.11.0", &files, Some(&home), &[]);
+        assert_eq!(impact.files, vec!["/home/user/.tool-versions"]);
+        assert!(impact.is_global);
+    }
+
+    #[test]
+    fn test_ref_pins_match_either_spelling() {
+        let home = file("/home/user/.tool-versions", &["ref:abc123"]);
+        assert!(pins_first(&home, "nodejs", "ref:abc123"));
+        assert!(pins_first(&home, "nodejs", "ref-abc123"));
+        assert!(!pins_first(&home, "nodejs", "ref:def456"));
+
+        let impact = uninstall_impact(
+            "nodejs",
+            "ref:abc123",
+            std::slice::from_ref(&home),
+            Some(&home),
+            &[],
+        );
+        assert_eq!(impact.files, vec!["/home/user/.tool-versions"]);
+        assert!(impact.is_global);
+        assert!(matches!(
+            ensure_not_global(Some(&home), "nodejs", "ref-abc123"),
+            Err(AppError::InUse(_))
+        ));
+        assert!(ensure_not_global(Some(&home), "nodejs", "20.11.0").is_ok());
+        assert!(ensure_not_global(None, "nodejs", "ref:abc123").is_ok());
+    }
+}
@@ -0,0 +1,346 @@
+use std::path::Path;
+
+use toml_edit::DocumentMut;
+
+/// A tool pin found in another ecosystem's manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedPin {
+    pub tool: &'static str,
+    pub source: String,
+    pub constraint: String,
+}
+
+/// How a manifest constraint maps onto `asdf latest <name> <filter>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionFilter {
+    /// Already a concrete version; no lookup needed.
+    Exact(String),
+    /// Newest version starting with this prefix. `asdf latest` matches by
+    /// string prefix, so it ends in `.` to keep `3.1.` from taking `3.13`.
+    Prefix(String),
+    /// Newest version overall.
+    Latest,
+}
+
+/// Manifests in priority order: dedicated version files win over
+/// constraints embedded in package manifests.
+const MANIFESTS: &[(&str, &str)] = &[
+    (".nvmrc", "nodejs"),
+    (".node-version", "nodejs"),
+    ("package.json", "nodejs"),
+    (".python-version", "python"),
+    ("pyproject.toml", "python"),
+    ("go.mod", "golang"),
+    ("rust-toolchain.toml", "rust"),
+    ("rust-toolchain", "rust"),
+    (".ruby-version", "ruby"),
+    ("Gemfile", "ruby"),
+];
+
+/// Detect tool pins from the manifests present in `dir`.
+pub fn detect_pins(dir: &Path) -> Vec<DetectedPin> {
+    MANIFESTS
+        .iter()
+        .filter_map(|(file, tool)| {
+            let path = dir.join(file);
+            let content = std::fs::read_to_string(&path).ok()?;
+            let constraint = extract_constraint(file, &content)?;
+            Some(DetectedPin {
+                tool,
+                source: path.to_string_lossy().to_string(),
+                constraint,
+            })
+        })
+        .collect()
+}
+
+/// Pull the version constraint out of a manifest's content.
+pub fn extract_constraint(file: &str, content: &str) -> Option<String> {
+    let constraint = match file {
+        ".nvmrc" | ".node-version" | ".python-version" => first_line(content),
+        ".ruby-version" => first_line(content).map(|v| v.trim_start_matches("ruby-").to_string()),
+        "package.json" => serde_json::from_str::<serde_json::Value>(content)
+            .ok()?
+            .get("engines")?
+            .get("node")?
+            .as_str()
+            .map(str::to_string),
+        "pyproject.toml" => {
+            let doc = content.parse::<DocumentMut>().ok()?;
+            doc.get("project")
+                .and_then(|p| p.get("requires-python"))
+                .or_else(|| {
+                    doc.get("tool")?
+                        .get("poetry")?
+                        .get("dependencies")?
+                        .get("python")
+                })
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        }
+        "go.mod" => go_mod_version(content),
+        "rust-toolchain.toml" => toolchain_channel(content),
+        // The legacy file is either a bare channel or TOML.
+        "rust-toolchain" => toolchain_channel(content).or_else(|| first_line(content)),
+        "Gemfile" => gemfile_ruby(content),
+        _ => None,
+    }?;
+    let constraint = constraint.trim().to_string();
+    (!constraint.is_empty()).then_some(constraint)
+}
+
+fn first_line(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// `toolchain go1.22.1` pins a newer toolchain than the `go` directive.
+fn go_mod_version(content: &str) -> Option<String> {
+    let directive = |prefix: &str| {
+        content
+            .lines()
+            .find_map(|l| l.trim().strip_prefix(prefix).map(|v| v.trim().to_string()))
+    };
+    directive("toolchain go").or_else(|| directive("go "))
+}
+
+fn toolchain_channel(content: &str) -> Option<String> {
+    content
+        .parse::<DocumentMut>()
+        .ok()?
+        .get("toolchain")?
+        .get("channel")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// `ruby "3.3.0"` or `ruby '~> 3.2'`; `ruby file: ".ruby-version"` is
+/// covered by the `.ruby-version` detector.
+fn gemfile_ruby(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("ruby ")?;
+        if rest.contains("file:") {
+            return None;
+        }
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let inner = &rest[1..];
+        inner.find(quote).map(|end| inner[..end].to_string())
+    })
+}
+
+/// Map a manifest constraint to an `asdf latest` filter. Upper bounds win
+/// since they cap the newest acceptable line (`>=3.9,<3.13` becomes the
+/// `3.12` prefix), and the last `||` alternative is taken as the newest.
+/// Returns `None` for constraints asdf cannot express, such as `lts/*`.
+pub fn constraint_filter(constraint: &str) -> Option<VersionFilter> {
+    let constraint = constraint.rsplit("||").next().unwrap_or(constraint);
+
+    // Split into clauses, re-attaching operators written apart (`~> 3.2`).
+    let mut clauses: Vec<String> = Vec::new();
+    let mut pending = String::new();
+    for token in constraint.split([',', ' ']).filter(|t| !t.is_empty()) {
+        pending.push_str(token);
+        if !token.chars().all(|c| "<>=~^!".contains(c)) {
+            clauses.push(std::mem::take(&mut pending));
+        }
+    }
+
+    match clauses.as_slice() {
+        [] => single_filter(""),
+        _ => clauses
+            .iter()
+            .find_map(|c| upper_bound_filter(c))
+            .or_else(|| single_filter(&clauses[0])),
+    }
+}
+
+fn upper_bound_filter(clause: &str) -> Option<VersionFilter> {
+    if let Some(v) = clause.strip_prefix("<=") {
+        return Some(prefix_or_exact(v));
+    }
+    let v = clause.strip_prefix('<')?;
+    let mut parts = numeric_parts(v)?;
+    // `<3.13` -> `3.12`, `<4` / `<4.0` -> `3`
+    while parts.len() > 1 && parts.last() == Some(&0) {
+        parts.pop();
+    }
+    let last = parts.last_mut()?;
+    if *last == 0 {
+        return None;
+    }
+    *last -= 1;
+    Some(release_line(&parts))
+}
+
+fn single_filter(clause: &str) -> Option<VersionFilter> {
+    let clause = clause.trim();
+    if clause.contains('/') {
+        return None;
+    }
+    if clause.is_empty() || clause == "*" || clause == "latest" {
+        return Some(VersionFilter::Latest);
+    }
+    if clause.starts_with(">=") || clause.starts_with('>') {
+        return Some(VersionFilter::Latest);
+    }
+    // Bounds `upper_bound_filter` could not map, and exclusions, are not
+    // versions.
+    if clause.starts_with(['<', '!']) {
+        return None;
+    }
+    if let Some(v) = clause
+        .strip_prefix("~>")
+        .or_else(|| clause.strip_prefix("~="))
+    {
+        // Pessimistic / compatible release: drop the last component.
+        let mut parts = numeric_parts(v.trim())?;
+        if parts.len() > 1 {
+            parts.pop();
+        }
+        return Some(release_line(&parts));
+    }
+    if let Some(v) = clause.strip_prefix('~') {
+        let parts = numeric_parts(v)?;
+        return Some(release_line(&parts[..parts.len().min(2)]));
+    }
+    if let Some(v) = clause.strip_prefix('^') {
+        let parts = numeric_parts(v)?;
+        let keep = if parts[0] == 0 { 2 } else { 1 };
+        return Some(release_line(&parts[..parts.len().min(keep)]));
+    }
+    let bare = clause.trim_start_matches("==").trim_start_matches('=');
+    let bare = bare.strip_prefix('v').unwrap_or(bare);
+    if bare.starts_with(|c: char| c.is_ascii_digit())
+        && let Some(idx) = bare.find(['x', 'X', '*'])
+    {
+        return Some(VersionFilter::Prefix(format!(
+            "{}.",
+            bare[..idx].trim_end_matches('.')
+        )));
+    }
+    Some(prefix_or_exact(bare))
+}
+
+/// Fully specified versions (and channels like `stable`) are exact;
+/// partial ones like `20` or `3.12` are prefixes.
+fn prefix_or_exact(version: &str) -> VersionFilter {
+    let version = version.trim();
+    match numeric_parts(version) {
+        Some(parts)
+            if parts.len() < 3 && version.chars().all(|c| c.is_ascii_digit() || c == '.') =>
+        {
+            VersionFilter::Prefix(format!("{}.", version.trim_end_matches('.')))
+        }
+        _ => VersionFilter::Exact(version.to_string()),
+    }
+}
+
+fn numeric_parts(version: &str) -> Option<Vec<u64>> {
+    let parts: Vec<u64> = version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map_while(|p| p.parse().ok())
+        .collect();
+    (!parts.is_empty()).then_some(parts)
+}
+
+/// Prefix filter for the release line `parts`, e.g. `3.12.`.
+fn release_line(parts: &[u64]) -> VersionFilter {
+    VersionFilter::Prefix(parts.iter().map(|p| format!("{p}.")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_constraint() {
+        assert_eq!(
+            extract_constraint("package.json", r#"{"engines": {"node": ">=18"}}"#),
+            Some(">=18".to_string())
+        );
+        assert_eq!(
+            extract_constraint(
+                "pyproject.toml",
+                "[project]\nname = \"app\"\nrequires-python = \">=3.9,<3.13\"\n"
+            ),
+            Some(">=3.9,<3.13".to_string())
+        );
+        assert_eq!(
+            extract_constraint("go.mod", "module x\n\ngo 1.21\n\ntoolchain go1.22.1\n"),
+            Some("1.22.1".to_string())
+        );
+        assert_eq!(
+            extract_constraint("Gemfile", "source 'https://rubygems.org'\nruby '~> 3.2'\n"),
+            Some("~> 3.2".to_string())
+        );
+        assert_eq!(
+            extract_constraint(".ruby-version", "ruby-3.3.0\n"),
+            Some("3.3.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_constraint_filter() {
+        use VersionFilter::*;
+        assert_eq!(
+            constraint_filter("20.11.0"),
+            Some(Exact("20.11.0".to_string()))
+        );
+        assert_eq!(constraint_filter("v20"), Some(Prefix("20.".to_string())));
+        assert_eq!(constraint_filter("^20.11"), Some(Prefix("20.".to_string())));
+        assert_eq!(
+            constraint_filter("~18.17.0"),
+            Some(Prefix("18.17.".to_string()))
+        );
+        assert_eq!(constraint_filter("~> 3.2"), Some(Prefix("3.".to_string())));
+        assert_eq!(constraint_filter("18.x"), Some(Prefix("18.".to_string())));
+        assert_eq!(constraint_filter(">=18"), Some(Latest));
+        assert_eq!(
+            constraint_filter(">=3.9,<3.13"),
+            Some(Prefix("3.12.".to_string()))
+        );
+        assert_eq!(
+            constraint_filter(">=18 <21"),
+            Some(Prefix("20.".to_string()))
+        );
+        assert_eq!(
+            constraint_filter("^18 || ^20"),
+            Some(Prefix("20.".to_string()))
+        );
+        assert_eq!(
+            constraint_filter("stable"),
+            Some(Exact("stable".to_string()))
+        );
+        assert_eq!(constraint_filter("lts/*"), None);
+    }
+
+    #[test]
+    fn test_constraint_filter_single_upper_bound() {
+        use VersionFilter::*;
+        assert_eq!(
+            constraint_filter("<3.13"),
+            Some(Prefix("3.12.".to_string()))
+        );
+        assert_eq!(
+            constraint_filter("<=3.12"),
+            Some(Prefix("3.12.".to_string()))
+        );
+        assert_eq!(
+            constraint_filter("<= 3.12.4"),
+            Some(Exact("3.12.4".to_string()))
+        );
+        assert_eq!(constraint_filter("<0"), None);
+        // The prefix ends at a component so 3.13 is not taken for 3.1.
+        assert_eq!(constraint_filter("<3.2"), Some(Prefix("3.1.".to_string())));
+        assert_eq!(
+            constraint_filter("~18.1.0"),
+            Some(Prefix("18.1.".to_string()))
+        );
+    }
+}
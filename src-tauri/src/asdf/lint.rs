@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::asdf::parser;
+use crate::types::{LintDiagnostic, LintSeverity};
+
+/// Local asdf state that `.tool-versions` entries are checked against.
+pub struct LintContext {
+    /// Installed plugin names.
+    pub plugins: Vec<String>,
+    /// Installed versions per plugin.
+    pub installed: HashMap<String, Vec<String>>,
+    /// Available versions per plugin, only for plugins whose `list all`
+    /// result is cached.
+    pub available: HashMap<String, Vec<String>>,
+}
+
+/// asdf plugin names are lowercase alphanumerics, `-` and `_`.
+pub fn is_valid_plugin_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Check a `.tool-versions` file content against the local asdf state.
+/// Diagnostics are returned in line order.
+pub fn lint_tool_versions(content: &str, ctx: &LintContext) -> Vec<LintDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for (line, entry) in parser::parse_tool_versions_lines(content) {
+        let tool = entry.tool.as_str();
+        let mut push = |severity: LintSeverity, message: String| {
+            diagnostics.push(LintDiagnostic {
+                line,
+                severity,
+                tool: Some(tool.to_string()),
+                message,
+            });
+        };
+
+        if let Some(first) = seen.get(tool) {
+            push(
+                LintSeverity::Warning,
+                format!("duplicate entry for {tool}, first defined on line {first}"),
+            );
+            continue;
+        }
+        seen.insert(tool.to_string(), line);
+
+        if !is_valid_plugin_name(tool) {
+            push(LintSeverity::Error, format!("invalid tool name: {tool}"));
+            continue;
+        }
+
+        if entry.versions.is_empty() {
+            push(
+                LintSeverity::Error,
+                format!("no version specified for {tool}"),
+            );
+        }
+
+        let plugin_installed = ctx.plugins.iter().any(|p| p == tool);
+        if !plugin_installed {
+            push(
+                LintSeverity::Error,
+                format!("plugin {tool} is not installed"),
+            );
+        }
+
+        for version in &entry.versions {
+            if let Some(git_ref) = version.strip_prefix("ref:") {
+                if git_ref.is_empty() {
+                    push(LintSeverity::Error, "ref: is missing a git ref".to_string());
+                }
+                continue;
+            }
+            if let Some(path) = version.strip_prefix("path:") {
+                if path.is_empty() {
+                    push(LintSeverity::Error, "path: is missing a path".to_string());
+                } else if !(path.starts_with('/') || path.starts_with('~')) {
+                    push(
+                        LintSeverity::Error,
+                        format!("path: must be absolute, got {path}"),
+                    );
+                } else if path.starts_with('/') && !Path::new(path).exists() {
+                    push(LintSeverity::Warning, format!("path {path} does not exist"));
+                }
+                continue;
+            }
+            if version == "system" || !plugin_installed {
+                continue;
+            }
+
+            let installed = ctx
+                .installed
+                .get(tool)
+                .is_some_and(|vs| vs.iter().any(|v| v == version));
+            match ctx.available.get(tool) {
+                Some(available) if !installed && !available.iter().any(|v| v == version) => {
+                    push(
+                        LintSeverity::Error,
+                        format!("{tool} {version} is not an available version"),
+                    );
+                }
+                _ if !installed => {
+                    push(
+                        LintSeverity::Warning,
+                        format!("{tool} {version} is not installed"),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if plugin_installed && !ctx.available.contains_key(tool) {
+            push(
+                LintSeverity::Info,
+                format!("available versions for {tool} not loaded, skipped availability check"),
+            );
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> LintContext {
+        LintContext {
+            plugins: vec!["nodejs".to_string(), "python".to_string()],
+            installed: HashMap::from([("nodejs".to_string(), vec!["20.11.0".to_string()])]),
+            available: HashMap::from([(
+                "nodejs".to_string(),
+                vec!["20.11.0".to_string(), "21.0.0".to_string()],
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_lint_clean_file() {
+        let result = lint_tool_versions("nodejs 20.11.0\n", &context());
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_lint_reports_line_numbers_and_severities() {
+        let input = "# tools\nnodejs 21.0.0\nnodjs 20.11.0\nnodejs 20.11.0\n";
+        let result = lint_tool_versions(input, &context());
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].line, 2);
+        assert_eq!(result[0].severity, LintSeverity::Warning);
+        assert_eq!(result[1].line, 3);
+        assert_eq!(result[1].severity, LintSeverity::Error);
+        assert_eq!(result[2].line, 4);
+        assert!(result[2].message.contains("duplicate"));
+    }
+
+    #[test]
+    fn test_lint_unavailable_version() {
+        let result = lint_tool_versions("nodejs 99.0.0\n", &context());
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].severity, LintSeverity::Error);
+    }
+
+    #[test]
+    fn test_lint_malformed_ref_and_path() {
+        let result = lint_tool_versions("nodejs ref: path:relative/dir\n", &context());
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|d| d.severity == LintSeverity::Error));
+    }
+}
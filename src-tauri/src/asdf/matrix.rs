@@ -0,0 +1,81 @@
+use crate::types::{CurrentVersion, MatrixCell, VersionMatrix};
+
+/// Build a tool × project matrix from each project's resolved versions.
+/// Tools are sorted by name; projects keep their order.
+pub fn build_matrix(projects: Vec<(String, Vec<CurrentVersion>)>) -> VersionMatrix {
+    let mut tools: Vec<String> = projects
+        .iter()
+        .flat_map(|(_, versions)| versions.iter().map(|v| v.name.clone()))
+        .collect();
+    tools.sort();
+    tools.dedup();
+
+    let cells: Vec<Vec<Option<MatrixCell>>> = tools
+        .iter()
+        .map(|tool| {
+            projects
+                .iter()
+                .map(|(_, versions)| {
+                    versions
+                        .iter()
+                        .find(|v| &v.name == tool)
+                        .map(|v| MatrixCell {
+                            version: v.version.clone(),
+                            source: v.source.clone(),
+                            installed: v.installed,
+                        })
+                })
+                .collect()
+        })
+        .collect();
+    let missing = cells
+        .iter()
+        .flatten()
+        .flatten()
+        .filter(|c| !c.installed)
+        .count();
+
+    VersionMatrix {
+        tools,
+        projects: projects.into_iter().map(|(path, _)| path).collect(),
+        cells,
+        missing,
+        skipped_roots: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn current(name: &str, version: &str, installed: bool) -> CurrentVersion {
+        CurrentVersion {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: "/work/.tool-versions".to_string(),
+            installed,
+            release: None,
+        }
+    }
+
+    #[test]
+    fn test_build_matrix() {
+        let matrix = build_matrix(vec![
+            (
+                "/work/api".to_string(),
+                vec![
+                    current("python", "3.12.1", true),
+                    current("nodejs", "20.11.0", true),
+                ],
+            ),
+            (
+                "/work/web".to_string(),
+                vec![current("nodejs", "18.19.0", false)],
+            ),
+        ]);
+        assert_eq!(matrix.tools, vec!["nodejs", "python"]);
+        assert_eq!(matrix.cells[0][1].as_ref().unwrap().version, "18.19.0");
+        assert!(matrix.cells[1][1].is_none());
+        assert_eq!(matrix.missing, 1);
+    }
+}
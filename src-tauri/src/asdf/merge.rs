@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+
+use crate::asdf::versioning::compare_versions;
+use crate::types::{MergeStrategy, ToolVersion, ToolVersionChange, ToolVersionsDiff};
+
+/// Compare two parsed `.tool-versions` files. `added` holds tools only in
+/// `right`, `removed` tools only in `left`.
+pub fn diff_tool_versions(left: &[ToolVersion], right: &[ToolVersion]) -> ToolVersionsDiff {
+    let mut diff = ToolVersionsDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+    for l in left {
+        match right.iter().find(|r| r.tool == l.tool) {
+            Some(r) if r.versions != l.versions => diff.changed.push(ToolVersionChange {
+                tool: l.tool.clone(),
+                left: l.versions.clone(),
+                right: r.versions.clone(),
+            }),
+            Some(_) => {}
+            None => diff.removed.push(l.clone()),
+        }
+    }
+    for r in right {
+        if !left.iter().any(|l| l.tool == r.tool) {
+            diff.added.push(r.clone());
+        }
+    }
+    diff
+}
+
+/// Merge two parsed `.tool-versions` files. Tools keep the order of `left`,
+/// followed by tools only present in `right`. Conflicts are settled by
+/// `strategy`; `PreferNewer` compares the primary (first) version and keeps
+/// `left` on a tie.
+pub fn merge_tool_versions(
+    left: &[ToolVersion],
+    right: &[ToolVersion],
+    strategy: &MergeStrategy,
+) -> Vec<ToolVersion> {
+    let mut merged: Vec<ToolVersion> = left
+        .iter()
+        .map(|l| match right.iter().find(|r| r.tool == l.tool) {
+            Some(r) if prefer_right(l, r, strategy) => r.clone(),
+            _ => l.clone(),
+        })
+        .collect();
+    merged.extend(
+        right
+            .iter()
+            .filter(|r| !left.iter().any(|l| l.tool == r.tool))
+            .cloned(),
+    );
+    merged
+}
+
+fn prefer_right(left: &ToolVersion, right: &ToolVersion, strategy: &MergeStrategy) -> bool {
+    match strategy {
+        MergeStrategy::PreferLeft => false,
+        MergeStrategy::PreferRight => true,
+        MergeStrategy::PreferNewer => match (left.versions.first(), right.versions.first()) {
+            (Some(l), Some(r)) => compare_versions(r, l) == Ordering::Greater,
+            (None, Some(_)) => true,
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tv(tool: &str, versions: &[&str]) -> ToolVersion {
+        ToolVersion {
+            tool: tool.to_string(),
+            versions: versions.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_tool_versions() {
+        let left = vec![tv("nodejs", &["18.19.0"]), tv("ruby", &["3.3.0"])];
+        let right = vec![tv("nodejs", &["20.11.0"]), tv("python", &["3.12.1"])];
+        let diff = diff_tool_versions(&left, &right);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].tool, "python");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].tool, "ruby");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].left, vec!["18.19.0"]);
+        assert_eq!(diff.changed[0].right, vec!["20.11.0"]);
+    }
+
+    #[test]
+    fn test_merge_strategies() {
+        let left = vec![tv("nodejs", &["20.11.0"]), tv("python", &["3.11.7"])];
+        let right = vec![
+            tv("python", &["3.12.1"]),
+            tv("nodejs", &["18.19.0"]),
+            tv("ruby", &["3.3.0"]),
+        ];
+
+        let merged = merge_tool_versions(&left, &right, &MergeStrategy::PreferLeft);
+        assert_eq!(merged[1].versions, vec!["3.11.7"]);
+        assert_eq!(merged[2].tool, "ruby");
+
+        let merged = merge_tool_versions(&left, &right, &MergeStrategy::PreferRight);
+        assert_eq!(merged[0].versions, vec!["18.19.0"]);
+
+        let merged = merge_tool_versions(&left, &right, &MergeStrategy::PreferNewer);
+        assert_eq!(merged[0].versions, vec!["20.11.0"]);
+        assert_eq!(merged[1].versions, vec!["3.12.1"]);
+        assert_eq!(merged.len(), 3);
+    }
+}
@@ -1,2 +1,24 @@
+pub mod args;
+pub mod batch;
+pub mod cache;
+pub mod default_packages;
+pub mod disk;
+pub mod eol;
 pub mod executor;
+pub mod export;
+pub mod gc;
+pub mod impact;
+pub mod import;
+pub mod lint;
+pub mod matrix;
+pub mod merge;
 pub mod parser;
+pub mod plugin_git;
+pub mod query;
+pub mod releases;
+pub mod resolution;
+pub mod scan;
+pub mod search;
+pub mod shell_scope;
+pub mod upgrade;
+pub mod versioning;
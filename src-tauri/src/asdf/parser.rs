@@ -1,3 +1,4 @@
+use crate::asdf::args::{PluginName, VersionString};
 use crate::error::AppError;
 use crate::types::*;
 
@@ -27,6 +28,7 @@ pub fn parse_current(stdout: &str) -> Result<Vec<CurrentVersion>, AppError> {
             version,
             source,
             installed,
+            release: None,
         });
     }
     Ok(results)
@@ -148,9 +150,19 @@ pub fn parse_shim_versions(stdout: &str) -> Vec<ShimVersion> {
 
 /// Parse a `.tool-versions` file content.
 pub fn parse_tool_versions(content: &str) -> Vec<ToolVersion> {
+    parse_tool_versions_lines(content)
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+/// Parse a `.tool-versions` file content, keeping the 1-based line number
+/// of each entry for diagnostics.
+pub fn parse_tool_versions_lines(content: &str) -> Vec<(usize, ToolVersion)> {
     content
         .lines()
-        .filter_map(|line| {
+        .enumerate()
+        .filter_map(|(idx, line)| {
             // Strip comments
             let line = line.split('#').next().unwrap_or("").trim();
             if line.is_empty() {
@@ -162,11 +174,72 @@ pub fn parse_tool_versions(content: &str) -> Vec<ToolVersion> {
             }
             let tool = parts[0].to_string();
             let versions = parts[1..].iter().map(|s| s.to_string()).collect();
-            Some(ToolVersion { tool, versions })
+            Some((idx + 1, ToolVersion { tool, versions }))
         })
         .collect()
 }
 
+/// Serialize entries as `.tool-versions` content, one tool per line.
+pub fn format_tool_versions(entries: &[ToolVersion]) -> String {
+    let content = entries
+        .iter()
+        .map(|e| format!("{} {}", e.tool, e.versions.join(" ")))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if content.is_empty() {
+        content
+    } else {
+        format!("{content}\n")
+    }
+}
+
+/// Replace the primary (first) version of each `(tool, version)` pair in
+/// `.tool-versions` content, leaving comments, spacing and any fallback
+/// versions untouched. Taking validated pairs keeps a version from adding
+/// lines of its own.
+pub fn rewrite_tool_versions(content: &str, updates: &[(PluginName, VersionString)]) -> String {
+    content
+        .split_inclusive('\n')
+        .map(|line| {
+            let code_end = line.find('#').unwrap_or(line.len());
+            let code = &line[..code_end];
+            let mut tokens = token_spans(code).into_iter();
+            let (Some((_, tool)), Some((start, current))) = (tokens.next(), tokens.next()) else {
+                return line.to_string();
+            };
+            match updates.iter().find(|(t, _)| t.as_str() == tool) {
+                Some((_, version)) => format!(
+                    "{}{}{}",
+                    &line[..start],
+                    version.as_str(),
+                    &line[start + current.len()..]
+                ),
+                None => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Whitespace-separated tokens of `s` with their byte offsets.
+fn token_spans(s: &str) -> Vec<(usize, &str)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in s.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(begin)) => {
+                spans.push((begin, &s[begin..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        spans.push((begin, &s[begin..]));
+    }
+    spans
+}
+
 /// Parse `asdf info` output.
 /// Multi-line format with labeled sections.
 pub fn parse_asdf_info(stdout: &str) -> Result<AsdfInfo, AppError> {
@@ -238,6 +311,7 @@ pub fn parse_env(stdout: &str) -> Vec<EnvVar> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::asdf::args::parse_pairs;
 
     #[test]
     fn test_parse_current() {
@@ -349,6 +423,52 @@ mod tests {
         assert_eq!(result[0].versions, vec!["20.11.0"]);
     }
 
+    #[test]
+    fn test_parse_tool_versions_lines() {
+        let input = "# header\nnodejs 20.11.0\n\npython 3.12.1\n";
+        let result = parse_tool_versions_lines(input);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, 2);
+        assert_eq!(result[0].1.tool, "nodejs");
+        assert_eq!(result[1].0, 4);
+        assert_eq!(result[1].1.tool, "python");
+    }
+
+    #[test]
+    fn test_format_tool_versions() {
+        let entries = parse_tool_versions("nodejs 20.11.0\npython 3.12.1 3.11.7\n");
+        assert_eq!(
+            format_tool_versions(&entries),
+            "nodejs 20.11.0\npython 3.12.1 3.11.7\n"
+        );
+        assert_eq!(format_tool_versions(&[]), "");
+    }
+
+    #[test]
+    fn test_rewrite_tool_versions_keeps_comments() {
+        let input = "# pinned for CI\nnodejs  20.11.0 18.19.0 # LTS\npython 3.12.1\n";
+        let updates = parse_pairs(&[("nodejs".to_string(), "20.12.2".to_string())]).unwrap();
+        assert_eq!(
+            rewrite_tool_versions(input, &updates),
+            "# pinned for CI\nnodejs  20.12.2 18.19.0 # LTS\npython 3.12.1\n"
+        );
+        // Offsets stay right after tabs and multi-byte whitespace.
+        let input = "\u{3000}nodejs\t20.11.0\u{a0} 18.19.0\n";
+        assert_eq!(
+            rewrite_tool_versions(input, &updates),
+            "\u{3000}nodejs\t20.12.2\u{a0} 18.19.0\n"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_rejects_versions_that_add_lines() {
+        let upgrades = vec![("nodejs".to_string(), "20.12.2\nruby 3.3.0".to_string())];
+        assert!(matches!(
+            parse_pairs(&upgrades),
+            Err(AppError::ParseError(_))
+        ));
+    }
+
     #[test]
     fn test_parse_env() {
         let input = "ASDF_DIR=/home/user/.asdf\nASDF_DATA_DIR=/home/user/.asdf\nPATH=/usr/bin\n";
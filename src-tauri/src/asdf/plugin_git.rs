@@ -0,0 +1,218 @@
+use std::path::{Path, PathBuf};
+
+use crate::asdf::executor::run_git;
+use crate::error::AppError;
+use crate::types::{DriftStatus, GitHead, PluginDetails, PluginDrift};
+
+/// Callbacks asdf cannot install versions without.
+const REQUIRED_CALLBACKS: &[&str] = &["list-all", "install"];
+
+pub fn plugin_dir(data_dir: &Path, name: &str) -> PathBuf {
+    data_dir.join("plugins").join(name)
+}
+
+/// The checkout's HEAD commit with the branch and tags pointing at it.
+pub async fn head(dir: &Path) -> Result<GitHead, AppError> {
+    let output = run_git(&["log", "-1", "--format=%H%x00%cI%x00%D%x00%s"], dir).await?;
+    parse_head(&output.stdout).ok_or_else(|| {
+        AppError::ParseError(format!("unexpected git log output in {}", dir.display()))
+    })
+}
+
+/// Parse `git log -1 --format=%H%x00%cI%x00%D%x00%s`.
+pub fn parse_head(output: &str) -> Option<GitHead> {
+    let mut fields = output.trim_end_matches('\n').splitn(4, '\0');
+    let commit = fields.next().filter(|c| !c.is_empty())?.to_string();
+    let date = fields.next()?.to_string();
+    let refs = fields.next()?;
+    let message = fields.next().unwrap_or_default().to_string();
+
+    let mut branch = None;
+    let mut tags = Vec::new();
+    for name in refs.split(", ").filter(|r| !r.is_empty()) {
+        if let Some(b) = name.strip_prefix("HEAD -> ") {
+            branch = Some(b.to_string());
+        } else if let Some(tag) = name.strip_prefix("tag: ") {
+            tags.push(tag.to_string());
+        }
+    }
+    Some(GitHead {
+        commit,
+        branch,
+        tags,
+        date,
+        message,
+    })
+}
+
+/// The commit `rev` names in the checkout, without fetching.
+pub async fn resolve_commit(dir: &Path, rev: &str) -> Option<String> {
+    let spec = format!("{rev}^{{commit}}");
+    let output = run_git(&["rev-parse", "--verify", "--quiet", &spec], dir)
+        .await
+        .ok()?;
+    Some(output.stdout.trim().to_string()).filter(|c| !c.is_empty())
+}
+
+/// Compare a plugin's checkout with its pin. `dir` is `None` when the
+/// plugin is not installed.
+pub async fn drift(name: &str, dir: Option<&Path>, pin: Option<&str>) -> PluginDrift {
+    let checkout = dir.filter(|d| d.join(".git").exists());
+    let head = match checkout {
+        Some(d) => head(d).await.ok(),
+        None => None,
+    };
+    let pin_commit = match (checkout, pin) {
+        (Some(d), Some(pin)) => resolve_commit(d, pin).await,
+        _ => None,
+    };
+    PluginDrift {
+        name: name.to_string(),
+        pin: pin.map(str::to_string),
+        status: drift_status(
+            dir.is_some(),
+            pin.is_some(),
+            head.as_ref(),
+            pin_commit.as_deref(),
+        ),
+        pin_commit,
+        head,
+    }
+}
+
+fn drift_status(
+    installed: bool,
+    pinned: bool,
+    head: Option<&GitHead>,
+    pin_commit: Option<&str>,
+) -> DriftStatus {
+    match (installed, pinned, head, pin_commit) {
+        (_, false, _, _) => DriftStatus::Unpinned,
+        (false, true, _, _) => DriftStatus::NotInstalled,
+        (true, true, Some(head), Some(commit)) if head.commit == commit => DriftStatus::Matches,
+        (true, true, Some(_), Some(_)) => DriftStatus::Drifted,
+        _ => DriftStatus::Unresolved,
+    }
+}
+
+/// Everything about an installed plugin that can be read from its
+/// checkout without the network.
+pub async fn details(data_dir: &Path, name: &str) -> Result<PluginDetails, AppError> {
+    let dir = plugin_dir(data_dir, name);
+    if !dir.is_dir() {
+        return Err(AppError::ConfigError(format!(
+            "plugin {name} is not installed"
+        )));
+    }
+
+    let (head, remote_url, dirty) = if dir.join(".git").exists() {
+        let head = head(&dir).await?;
+        let remote_url = run_git(&["remote", "get-url", "origin"], &dir)
+            .await
+            .ok()
+            .map(|o| o.stdout.trim().to_string())
+            .filter(|url| !url.is_empty());
+        let status = run_git(&["status", "--porcelain"], &dir).await?;
+        (Some(head), remote_url, !status.stdout.trim().is_empty())
+    } else {
+        (None, None, false)
+    };
+
+    let callbacks = callbacks(&dir);
+    let missing_callbacks = REQUIRED_CALLBACKS
+        .iter()
+        .filter(|c| !callbacks.iter().any(|have| have == *c))
+        .map(|c| c.to_string())
+        .collect();
+
+    Ok(PluginDetails {
+        name: name.to_string(),
+        path: dir.to_string_lossy().to_string(),
+        head,
+        remote_url,
+        dirty,
+        callbacks,
+        missing_callbacks,
+        readme: readme(&dir),
+    })
+}
+
+/// Scripts in the plugin's `bin/`, sorted.
+fn callbacks(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir.join("bin")) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+/// The top-level README, preferring Markdown when there are several.
+fn readme(dir: &Path) -> Option<String> {
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.is_file()
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.to_ascii_lowercase().starts_with("readme"))
+        })
+        .collect();
+    candidates.sort_by_key(|p| p.extension().is_none_or(|e| !e.eq_ignore_ascii_case("md")));
+    candidates
+        .iter()
+        .find_map(|p| std::fs::read_to_string(p).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_head() {
+        let head = parse_head(
+            "3f2a9c1e\u{0}2024-03-01T10:12:00+01:00\u{0}HEAD -> master, tag: v1.2.0, origin/master\u{0}Fix latest-stable on macOS\n",
+        )
+        .unwrap();
+        assert_eq!(head.commit, "3f2a9c1e");
+        assert_eq!(head.branch.as_deref(), Some("master"));
+        assert_eq!(head.tags, vec!["v1.2.0"]);
+        assert_eq!(head.message, "Fix latest-stable on macOS");
+
+        let detached = parse_head("3f2a9c1e\u{0}2024-03-01T10:12:00+01:00\u{0}HEAD\u{0}x").unwrap();
+        assert_eq!(detached.branch, None);
+        assert!(detached.tags.is_empty());
+        assert!(parse_head("").is_none());
+    }
+
+    #[test]
+    fn test_drift_status() {
+        let head = parse_head("3f2a9c1e\u{0}2024-03-01T10:12:00+01:00\u{0}HEAD\u{0}x").unwrap();
+        assert_eq!(
+            drift_status(true, false, Some(&head), None),
+            DriftStatus::Unpinned
+        );
+        assert_eq!(
+            drift_status(false, true, None, None),
+            DriftStatus::NotInstalled
+        );
+        assert_eq!(
+            drift_status(true, true, Some(&head), Some("3f2a9c1e")),
+            DriftStatus::Matches
+        );
+        assert_eq!(
+            drift_status(true, true, Some(&head), Some("a81bc07d")),
+            DriftStatus::Drifted
+        );
+        assert_eq!(
+            drift_status(true, true, Some(&head), None),
+            DriftStatus::Unresolved
+        );
+    }
+}
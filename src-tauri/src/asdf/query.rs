@@ -0,0 +1,45 @@
+use crate::asdf::args::with_positionals;
+use crate::asdf::executor::run_asdf;
+use crate::asdf::{cache, parser};
+use crate::error::AppError;
+
+/// Names of installed plugins.
+pub async fn plugin_names(asdf_path: Option<&str>) -> Result<Vec<String>, AppError> {
+    let output = run_asdf(&["plugin", "list"], None, asdf_path).await?;
+    Ok(parser::parse_plugin_list(&output.stdout)?
+        .into_iter()
+        .map(|p| p.name)
+        .collect())
+}
+
+/// Installed versions of a plugin. A plugin with nothing installed makes
+/// `asdf list` exit non-zero, which is reported as an empty list.
+pub async fn installed_versions(name: &str, asdf_path: Option<&str>) -> Vec<String> {
+    match run_asdf(&with_positionals(&["list"], &[name]), None, asdf_path).await {
+        Ok(output) => parser::parse_list_installed(&output.stdout)
+            .into_iter()
+            .map(|(v, _)| v)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// All available versions of a plugin, from the `list_all` cache when
+/// present, otherwise fetched once and cached.
+pub async fn available_versions(
+    name: &str,
+    asdf_path: Option<&str>,
+) -> Result<Vec<String>, AppError> {
+    if let Some(versions) = cache::cached_list_all(name) {
+        return Ok(versions);
+    }
+    let output = run_asdf(
+        &with_positionals(&["list", "all"], &[name]),
+        None,
+        asdf_path,
+    )
+    .await?;
+    let versions = parser::parse_list_all(&output.stdout);
+    cache::store_list_all(name, &versions);
+    Ok(versions)
+}
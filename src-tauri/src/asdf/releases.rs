@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::config::app_config;
+use crate::error::AppError;
+use crate::types::{ReleaseInfo, VersionEntry};
+
+/// Release lines per plugin name.
+pub type ReleaseData = HashMap<String, Vec<ReleaseInfo>>;
+
+const BUNDLED: &str = include_str!("release_metadata.json");
+
+/// Last override error logged, so a broken file is logged once rather than
+/// on every listing.
+static LOGGED_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+/// The bundled dataset with the user's override file applied. The file is
+/// re-read on every call so edits apply without a restart. Annotations are
+/// optional, so a malformed file falls back to the bundled data; the
+/// problem is logged and reported by [`override_error`].
+pub fn load() -> ReleaseData {
+    let mut data: ReleaseData = serde_json::from_str(BUNDLED).unwrap_or_default();
+    match read_overrides() {
+        Ok(Some(overrides)) => merge(&mut data, overrides),
+        Ok(None) => {}
+        Err(e) => {
+            let message = e.to_string();
+            if let Ok(mut logged) = LOGGED_ERROR.lock()
+                && logged.as_deref() != Some(message.as_str())
+            {
+                log::warn!("using bundled release data: {message}");
+                *logged = Some(message);
+            }
+        }
+    }
+    data
+}
+
+/// Why the override file is being ignored, if it is.
+pub fn override_error() -> Option<String> {
+    read_overrides().err().map(|e| e.to_string())
+}
+
+/// The user's override file, `None` when there is none.
+fn read_overrides() -> Result<Option<ReleaseData>, AppError> {
+    let Ok(path) = app_config::release_metadata_path() else {
+        return Ok(None);
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    parse_overrides(&path, &content).map(Some)
+}
+
+fn parse_overrides(path: &Path, content: &str) -> Result<ReleaseData, AppError> {
+    serde_json::from_str(content)
+        .map_err(|e| AppError::ConfigError(format!("invalid {}: {e}", path.display())))
+}
+
+/// Override entries replace bundled ones with the same cycle; new cycles
+/// and tools are added.
+pub fn merge(data: &mut ReleaseData, overrides: ReleaseData) {
+    for (tool, cycles) in overrides {
+        let existing = data.entry(tool).or_default();
+        for cycle in cycles {
+            match existing.iter_mut().find(|c| c.cycle == cycle.cycle) {
+                Some(slot) => *slot = cycle,
+                None => existing.push(cycle),
+            }
+        }
+    }
+}
+
+/// The release line `version` belongs to. Distribution prefixes such as
+/// `temurin-` are skipped, and the longest matching cycle wins so `3.12.1`
+/// is not taken for `3.1`.
+pub fn lookup<'a>(data: &'a ReleaseData, tool: &str, version: &str) -> Option<&'a ReleaseInfo> {
+    let version = version.trim_start_matches(|c: char| !c.is_ascii_digit());
+    data.get(tool)?
+        .iter()
+        .filter(|info| {
+            version
+                .strip_prefix(info.cycle.as_str())
+                .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        .max_by_key(|info| info.cycle.len())
+}
+
+/// Pair each version with its release line.
+pub fn annotate(data: &ReleaseData, tool: &str, versions: Vec<String>) -> Vec<VersionEntry> {
+    versions
+        .into_iter()
+        .map(|version| VersionEntry {
+            release: lookup(data, tool, &version).cloned(),
+            version,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled() -> ReleaseData {
+        serde_json::from_str(BUNDLED).unwrap()
+    }
+
+    #[test]
+    fn test_lookup() {
+        let data = bundled();
+        let node = lookup(&data, "nodejs", "20.11.0").unwrap();
+        assert!(node.lts);
+        assert_eq!(node.eol.as_deref(), Some("2026-04-30"));
+        assert!(!lookup(&data, "nodejs", "21.7.3").unwrap().lts);
+        assert_eq!(lookup(&data, "python", "3.12.1").unwrap().cycle, "3.12");
+        assert_eq!(lookup(&data, "python", "3.1.5"), None);
+        assert_eq!(
+            lookup(&data, "java", "temurin-21.0.2+13.0.LTS")
+                .unwrap()
+                .cycle,
+            "21"
+        );
+        assert_eq!(
+            lookup(&data, "elixir", "1.16.1-otp-26").unwrap().cycle,
+            "1.16"
+        );
+        assert_eq!(lookup(&data, "nodejs", "system"), None);
+    }
+
+    #[test]
+    fn test_merge_overrides_cycle() {
+        let mut data = bundled();
+        let path = Path::new("/home/user/.config/asdf-gui/releases.json");
+        let overrides = parse_overrides(
+            path,
+            r#"{"nodejs": [{"cycle": "20", "lts": true, "eol": "2026-05-01"}],
+                "deno": [{"cycle": "2"}]}"#,
+        )
+        .unwrap();
+        merge(&mut data, overrides);
+        let node = lookup(&data, "nodejs", "20.11.0").unwrap();
+        assert_eq!(node.eol.as_deref(), Some("2026-05-01"));
+        assert_eq!(node.release_date, None);
+        assert!(lookup(&data, "deno", "2.1.4").is_some());
+        assert!(matches!(
+            parse_overrides(path, "{\"nodejs\": "),
+            Err(AppError::ConfigError(_))
+        ));
+    }
+}
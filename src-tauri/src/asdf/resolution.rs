@@ -0,0 +1,370 @@
+use std::path::{Path, PathBuf};
+
+use crate::asdf::args::PluginName;
+use crate::asdf::executor::run_plugin_script;
+use crate::asdf::{parser, shell_scope};
+use crate::config::app_config;
+use crate::config::env::user_env_var;
+use crate::error::AppError;
+use crate::types::{
+    ResolutionCandidate, ResolutionExplanation, ResolutionSourceKind, ToolVersion, ToolVersionsFile,
+};
+
+/// Inputs to asdf's version lookup that come from the user's environment.
+pub struct ResolutionSettings {
+    /// Value of `ASDF_<TOOL>_VERSION`, if set.
+    pub env_value: Option<String>,
+    /// Version file name, normally `.tool-versions`.
+    pub filename: String,
+    /// Legacy file names the plugin reads; empty when legacy files are off.
+    pub legacy_filenames: Vec<String>,
+    pub home: Option<PathBuf>,
+}
+
+/// Name of the env var asdf checks for a tool override,
+/// e.g. `ASDF_NODEJS_VERSION` or `ASDF_GOLANG_CI_LINT_VERSION`.
+pub fn env_var_name(tool: &str) -> String {
+    format!("ASDF_{}_VERSION", tool.to_uppercase().replace('-', "_"))
+}
+
+/// Rebuild asdf's lookup chain for `tool` in `dir`: the env var, then each
+/// directory up to `/` (version file before legacy files), then the home
+/// version file. The first matching candidate wins.
+pub fn explain_resolution(
+    dir: &Path,
+    tool: &str,
+    settings: &ResolutionSettings,
+) -> ResolutionExplanation {
+    let mut candidates = vec![ResolutionCandidate {
+        kind: ResolutionSourceKind::EnvVar,
+        location: env_var_name(tool),
+        exists: settings.env_value.is_some(),
+        matched: settings.env_value.is_some(),
+        versions: settings
+            .env_value
+            .as_deref()
+            .map(|v| v.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default(),
+    }];
+
+    let mut walked_home = false;
+    for ancestor in dir.ancestors() {
+        if settings.home.as_deref() == Some(ancestor) {
+            walked_home = true;
+        }
+        candidates.push(version_file_candidate(
+            ResolutionSourceKind::ToolVersions,
+            &ancestor.join(&settings.filename),
+            tool,
+        ));
+        for legacy in &settings.legacy_filenames {
+            candidates.push(legacy_file_candidate(&ancestor.join(legacy)));
+        }
+    }
+
+    if let Some(home) = settings.home.as_deref()
+        && !walked_home
+    {
+        candidates.push(version_file_candidate(
+            ResolutionSourceKind::Home,
+            &home.join(&settings.filename),
+            tool,
+        ));
+    }
+
+    let winner = candidates.iter().position(|c| c.matched);
+    ResolutionExplanation {
+        tool: tool.to_string(),
+        dir: dir.to_string_lossy().to_string(),
+        candidates,
+        winner,
+    }
+}
+
+/// Tools named in any version file asdf consults from `dir`: each
+/// directory up to `/`, then the home file.
+pub fn chain_tools(dir: &Path, filename: &str, home: Option<&Path>) -> Vec<String> {
+    let files = dir
+        .ancestors()
+        .map(|d| d.join(filename))
+        .chain(home.map(|h| h.join(filename)));
+    let mut tools: Vec<String> = Vec::new();
+    for path in files {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for entry in parser::parse_tool_versions(&content) {
+            if !tools.contains(&entry.tool) {
+                tools.push(entry.tool);
+            }
+        }
+    }
+    tools
+}
+
+/// What asdf resolves in `dir` for every tool it could resolve there: tools
+/// in version files up to `/` and in the home file, those set through
+/// `ASDF_<TOOL>_VERSION` (Shell scope included), and with legacy files on,
+/// every plugin in `plugins`. Each tool's versions come from the source
+/// that wins, grouped per source.
+pub async fn effective_pins(
+    dir: &str,
+    plugins: &[String],
+) -> Result<Vec<ToolVersionsFile>, AppError> {
+    let filename = app_config::tool_versions_filename();
+    let mut tools = chain_tools(Path::new(dir), &filename, dirs::home_dir().as_deref());
+    let from_env = plugins
+        .iter()
+        .filter(|p| user_env_var(&env_var_name(p)).is_some())
+        .cloned();
+    for tool in shell_scope::overrides_for(dir).into_keys().chain(from_env) {
+        if !tools.contains(&tool) {
+            tools.push(tool);
+        }
+    }
+    // Legacy files can pin any plugin without a version file naming it.
+    if legacy_files_enabled()? {
+        for plugin in plugins {
+            if !tools.contains(plugin) {
+                tools.push(plugin.clone());
+            }
+        }
+    }
+
+    let mut resolved = Vec::new();
+    for tool in tools {
+        // Names asdf would reject are reported by the linter, not resolved.
+        let Ok(name) = PluginName::parse(tool.as_str()) else {
+            continue;
+        };
+        let settings = resolution_settings(dir, &name).await?;
+        resolved.push((tool, settings));
+    }
+    Ok(winning_pins(Path::new(dir), &resolved))
+}
+
+/// The winning candidate of each `(tool, settings)` in `dir`, as one entry
+/// per tool in a file per source. `path` is the file, or the env var name
+/// for an override.
+pub fn winning_pins(
+    dir: &Path,
+    resolved: &[(String, ResolutionSettings)],
+) -> Vec<ToolVersionsFile> {
+    let mut files: Vec<ToolVersionsFile> = Vec::new();
+    for (tool, settings) in resolved {
+        let explanation = explain_resolution(dir, tool, settings);
+        let Some(winner) = explanation.winner else {
+            continue;
+        };
+        let candidate = &explanation.candidates[winner];
+        let entry = ToolVersion {
+            tool: tool.clone(),
+            versions: candidate.versions.clone(),
+        };
+        match files.iter_mut().find(|f| f.path == candidate.location) {
+            Some(file) => file.entries.push(entry),
+            None => files.push(ToolVersionsFile {
+                path: candidate.location.clone(),
+                entries: vec![entry],
+            }),
+        }
+    }
+    files
+}
+
+pub fn legacy_files_enabled() -> Result<bool, AppError> {
+    Ok(app_config::read_asdfrc()?
+        .iter()
+        .any(|(k, v)| k == "legacy_version_file" && v == "yes"))
+}
+
+/// What asdf resolves `tool` in `dir` from: a Shell-scope override before
+/// the process environment, and legacy files when enabled.
+pub async fn resolution_settings(
+    dir: &str,
+    tool: &PluginName,
+) -> Result<ResolutionSettings, AppError> {
+    let legacy_filenames = if legacy_files_enabled()? {
+        legacy_filenames(tool).await
+    } else {
+        Vec::new()
+    };
+    let env_name = env_var_name(tool.as_str());
+    Ok(ResolutionSettings {
+        env_value: shell_scope::env_for(dir)
+            .remove(&env_name)
+            .or_else(|| user_env_var(&env_name)),
+        filename: app_config::tool_versions_filename(),
+        legacy_filenames,
+        home: dirs::home_dir(),
+    })
+}
+
+/// Legacy version file names declared by the plugin's
+/// `bin/list-legacy-filenames` callback, if it has one.
+pub async fn legacy_filenames(tool: &PluginName) -> Vec<String> {
+    let Ok(data_dir) = app_config::asdf_data_dir() else {
+        return Vec::new();
+    };
+    let script = data_dir
+        .join("plugins")
+        .join(tool.as_str())
+        .join("bin")
+        .join("list-legacy-filenames");
+    if !script.exists() {
+        return Vec::new();
+    }
+    match run_plugin_script(&script).await {
+        Ok(output) => output
+            .stdout
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+        Err(e) => {
+            log::warn!("ignoring legacy files for {}: {e}", tool.as_str());
+            Vec::new()
+        }
+    }
+}
+
+/// Closest version file at or above `dir`, as `asdf set --parent` finds it.
+pub fn nearest_version_file(dir: &Path, filename: &str) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|d| d.join(filename))
+        .find(|candidate| candidate.exists())
+}
+
+fn version_file_candidate(
+    kind: ResolutionSourceKind,
+    path: &Path,
+    tool: &str,
+) -> ResolutionCandidate {
+    let content = std::fs::read_to_string(path).ok();
+    let versions = content
+        .as_deref()
+        .and_then(|c| {
+            parser::parse_tool_versions(c)
+                .into_iter()
+                .find(|e| e.tool == tool)
+        })
+        .map(|e| e.versions)
+        .unwrap_or_default();
+    ResolutionCandidate {
+        kind,
+        location: path.to_string_lossy().to_string(),
+        exists: content.is_some(),
+        matched: !versions.is_empty(),
+        versions,
+    }
+}
+
+fn legacy_file_candidate(path: &Path) -> ResolutionCandidate {
+    let content = std::fs::read_to_string(path).ok();
+    let versions: Vec<String> = content
+        .as_deref()
+        .map(|c| c.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default();
+    ResolutionCandidate {
+        kind: ResolutionSourceKind::LegacyFile,
+        location: path.to_string_lossy().to_string(),
+        exists: content.is_some(),
+        matched: !versions.is_empty(),
+        versions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(env_var_name("nodejs"), "ASDF_NODEJS_VERSION");
+        assert_eq!(
+            env_var_name("golang-ci-lint"),
+            "ASDF_GOLANG_CI_LINT_VERSION"
+        );
+    }
+
+    #[test]
+    fn test_env_var_wins() {
+        let settings = ResolutionSettings {
+            env_value: Some("18.19.0".to_string()),
+            filename: ".tool-versions".to_string(),
+            legacy_filenames: Vec::new(),
+            home: None,
+        };
+        let result = explain_resolution(Path::new("/"), "nodejs", &settings);
+        assert_eq!(result.winner, Some(0));
+        assert_eq!(result.candidates[0].versions, vec!["18.19.0"]);
+        assert_eq!(
+            result.candidates[1].kind,
+            ResolutionSourceKind::ToolVersions
+        );
+    }
+
+    #[test]
+    fn test_chain_tools_reads_parents_and_home() {
+        let root = std::env::temp_dir().join(format!("asdf-gui-chain-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("work/app");
+        let home = root.join("home");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(project.join(".tool-versions"), "nodejs 20.11.0\n").unwrap();
+        std::fs::write(
+            root.join("work/.tool-versions"),
+            "python 3.12.1\nnodejs 18.19.0\n",
+        )
+        .unwrap();
+        std::fs::write(home.join(".tool-versions"), "ruby 3.3.0\n").unwrap();
+
+        let tools = chain_tools(&project, ".tool-versions", Some(&home));
+        assert_eq!(tools, vec!["nodejs", "python", "ruby"]);
+
+        // The parent file still decides python when the project has none.
+        let settings = ResolutionSettings {
+            env_value: None,
+            filename: ".tool-versions".to_string(),
+            legacy_filenames: Vec::new(),
+            home: Some(home),
+        };
+        let python = explain_resolution(&project, "python", &settings);
+        let winner = &python.candidates[python.winner.unwrap()];
+        assert_eq!(winner.versions, vec!["3.12.1"]);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_winning_pins_include_parent_and_legacy_files() {
+        let root = std::env::temp_dir().join(format!("asdf-gui-pins-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = root.join("work/app");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(root.join("work/.tool-versions"), "python 3.12.1\n").unwrap();
+        std::fs::write(project.join(".nvmrc"), "20.11.0\n").unwrap();
+
+        let settings = |legacy: &[&str]| ResolutionSettings {
+            env_value: None,
+            filename: ".tool-versions".to_string(),
+            legacy_filenames: legacy.iter().map(|s| s.to_string()).collect(),
+            home: None,
+        };
+        let resolved = vec![
+            ("nodejs".to_string(), settings(&[".nvmrc"])),
+            ("python".to_string(), settings(&[])),
+            ("ruby".to_string(), settings(&[])),
+        ];
+        let files = winning_pins(&project, &resolved);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, project.join(".nvmrc").to_string_lossy());
+        assert_eq!(files[0].entries[0].tool, "nodejs");
+        assert_eq!(files[0].entries[0].versions, vec!["20.11.0"]);
+        assert_eq!(
+            files[1].path,
+            root.join("work/.tool-versions").to_string_lossy()
+        );
+        assert_eq!(files[1].entries[0].versions, vec!["3.12.1"]);
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
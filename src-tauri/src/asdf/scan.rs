@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use ignore::WalkBuilder;
+use ignore::overrides::OverrideBuilder;
+
+use crate::asdf::executor::blocking;
+use crate::asdf::parser;
+use crate::asdf::resolution;
+use crate::config::app_config;
+use crate::error::AppError;
+use crate::types::{ToolPin, ToolVersionsFile};
+
+/// Walk `root` for version files named `filename`, honoring `.gitignore`
+/// and the extra `ignore_globs` (gitignore syntax, relative to `root`).
+/// Files that cannot be read are skipped, but a missing `root` is an error
+/// rather than an empty result. Results are sorted by path.
+pub fn scan_tool_versions(
+    root: &Path,
+    filename: &str,
+    ignore_globs: &[String],
+) -> Result<Vec<ToolVersionsFile>, AppError> {
+    if !root.is_dir() {
+        return Err(AppError::ConfigError(format!(
+            "scan root {} is not a directory",
+            root.display()
+        )));
+    }
+    let mut overrides = OverrideBuilder::new(root);
+    for glob in ignore_globs {
+        overrides
+            .add(&format!("!{glob}"))
+            .map_err(|e| AppError::ParseError(format!("invalid ignore glob {glob}: {e}")))?;
+    }
+    let overrides = overrides
+        .build()
+        .map_err(|e| AppError::ParseError(e.to_string()))?;
+
+    let walker = WalkBuilder::new(root)
+        // .tool-versions and most overrides are dotfiles
+        .hidden(false)
+        .require_git(false)
+        .overrides(overrides)
+        .filter_entry(|e| e.file_name() != ".git")
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker.flatten() {
+        if entry.file_type().is_some_and(|t| t.is_file())
+            && entry.file_name() == filename
+            && let Ok(content) = std::fs::read_to_string(entry.path())
+        {
+            files.push(ToolVersionsFile {
+                path: entry.path().to_string_lossy().to_string(),
+                entries: parser::parse_tool_versions(&content),
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// The version file in the home directory, which sets global versions.
+pub fn home_version_file() -> Option<ToolVersionsFile> {
+    let path = dirs::home_dir()?.join(app_config::tool_versions_filename());
+    let content = std::fs::read_to_string(&path).ok()?;
+    Some(ToolVersionsFile {
+        path: path.to_string_lossy().to_string(),
+        entries: parser::parse_tool_versions(&content),
+    })
+}
+
+/// Pins the app knows to be in use: the home version file, everything asdf
+/// resolves in the working directory and each recent project (parent and
+/// legacy files and env overrides included), and every version file under
+/// `roots`. One file per path, sorted. A missing root fails the call: garbage
+/// collection must not treat pins it cannot see as unused.
+pub async fn known_version_files(
+    roots: &[String],
+    plugins: &[String],
+) -> Result<Vec<ToolVersionsFile>, AppError> {
+    let filename = app_config::tool_versions_filename();
+    let config = app_config::read_config()?;
+
+    let mut files: Vec<ToolVersionsFile> = home_version_file().into_iter().collect();
+    let projects = config
+        .working_directory
+        .into_iter()
+        .chain(config.recent_projects.into_iter().map(|p| p.path));
+    for project in projects {
+        files.extend(resolution::effective_pins(&project, plugins).await?);
+    }
+    let roots = roots.to_vec();
+    files.extend(
+        blocking(move || {
+            let mut found = Vec::new();
+            for root in &roots {
+                found.extend(scan_tool_versions(Path::new(root), &filename, &[])?);
+            }
+            Ok::<_, AppError>(found)
+        })
+        .await??,
+    );
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    // Projects sharing a parent or home file each report the tools they
+    // resolve from it.
+    let mut merged: Vec<ToolVersionsFile> = Vec::new();
+    for file in files {
+        match merged.last_mut() {
+            Some(last) if last.path == file.path => {
+                for entry in file.entries {
+                    if !last.entries.iter().any(|e| e.tool == entry.tool) {
+                        last.entries.push(entry);
+                    }
+                }
+            }
+            _ => merged.push(file),
+        }
+    }
+    Ok(merged)
+}
+
+/// Group scanned files by (tool, version) to show which pins live where.
+pub fn aggregate_pins(files: &[ToolVersionsFile]) -> Vec<ToolPin> {
+    let mut pins: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for file in files {
+        for entry in &file.entries {
+            for version in &entry.versions {
+                pins.entry((entry.tool.clone(), version.clone()))
+                    .or_default()
+                    .push(file.path.clone());
+            }
+        }
+    }
+    pins.into_iter()
+        .map(|((tool, version), paths)| ToolPin {
+            tool,
+            version,
+            paths,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ToolVersion;
+
+    #[test]
+    fn test_scan_missing_root_is_an_error() {
+        let dir = std::env::temp_dir().join(format!("asdf-gui-scan-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("app")).unwrap();
+        std::fs::write(dir.join("app/.tool-versions"), "nodejs 20.11.0\n").unwrap();
+
+        let files = scan_tool_versions(&dir, ".tool-versions", &[]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(matches!(
+            scan_tool_versions(&dir.join("missing"), ".tool-versions", &[]),
+            Err(AppError::ConfigError(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_aggregate_pins() {
+        let files = vec![
+            ToolVersionsFile {
+                path: "/repo/a/.tool-versions".to_string(),
+                entries: vec![ToolVersion {
+                    tool: "nodejs".to_string(),
+                    versions: vec!["20.11.0".to_string()],
+                }],
+            },
+            ToolVersionsFile {
+                path: "/repo/b/.tool-versions".to_string(),
+                entries: vec![
+                    ToolVersion {
+                        tool: "nodejs".to_string(),
+                        versions: vec!["20.11.0".to_string()],
+                    },
+                    ToolVersion {
+                        tool: "python".to_string(),
+                        versions: vec!["3.12.1".to_string()],
+                    },
+                ],
+            },
+        ];
+        let pins = aggregate_pins(&files);
+        assert_eq!(pins.len(), 2);
+        assert_eq!(pins[0].tool, "nodejs");
+        assert_eq!(pins[0].paths.len(), 2);
+        assert_eq!(pins[1].tool, "python");
+        assert_eq!(pins[1].paths, vec!["/repo/b/.tool-versions"]);
+    }
+}
@@ -0,0 +1,149 @@
+use regex::Regex;
+
+use crate::asdf::versioning::{compare_versions, is_prerelease, major_line, sort_versions};
+use crate::error::AppError;
+use crate::types::{SearchMode, SortOrder, VersionGroup, VersionQuery};
+
+/// Versions matching `query`, sorted, plus the total match count and major
+/// line groups. The page is `query.offset..query.offset + query.limit`.
+pub fn search_versions(
+    versions: &[String],
+    query: &VersionQuery,
+) -> Result<(usize, Vec<String>, Vec<VersionGroup>), AppError> {
+    let matcher = Matcher::new(query)?;
+    let mut matches: Vec<String> = versions
+        .iter()
+        .filter(|v| query.include_prerelease || !is_prerelease(v))
+        .filter(|v| matcher.matches(v))
+        .cloned()
+        .collect();
+    sort_versions(&mut matches);
+    if matches!(query.order, SortOrder::Descending) {
+        matches.reverse();
+    }
+
+    let groups = if query.group_by_major {
+        group_by_major(&matches)
+    } else {
+        Vec::new()
+    };
+    let total = matches.len();
+    let page = matches
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect();
+    Ok((total, page, groups))
+}
+
+/// Groups in the order their lines first appear in `versions`.
+fn group_by_major(versions: &[String]) -> Vec<VersionGroup> {
+    let mut groups: Vec<VersionGroup> = Vec::new();
+    for version in versions {
+        let major = major_line(version);
+        match groups.iter_mut().find(|g| g.major == major) {
+            Some(group) => {
+                group.count += 1;
+                if compare_versions(version, &group.newest).is_gt() {
+                    group.newest = version.clone();
+                }
+            }
+            None => groups.push(VersionGroup {
+                major,
+                count: 1,
+                newest: version.clone(),
+            }),
+        }
+    }
+    groups
+}
+
+enum Matcher {
+    All,
+    Substring(String),
+    Regex(Regex),
+    Fuzzy(Vec<char>),
+}
+
+impl Matcher {
+    fn new(query: &VersionQuery) -> Result<Self, AppError> {
+        let Some(search) = query.search.as_deref().filter(|s| !s.is_empty()) else {
+            return Ok(Self::All);
+        };
+        Ok(match query.mode {
+            SearchMode::Substring => Self::Substring(search.to_lowercase()),
+            SearchMode::Regex => Self::Regex(
+                Regex::new(search)
+                    .map_err(|e| AppError::ParseError(format!("invalid regex: {e}")))?,
+            ),
+            SearchMode::Fuzzy => Self::Fuzzy(search.to_lowercase().chars().collect()),
+        })
+    }
+
+    fn matches(&self, version: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Substring(s) => version.to_lowercase().contains(s.as_str()),
+            Self::Regex(re) => re.is_match(version),
+            Self::Fuzzy(chars) => {
+                let mut haystack = version.chars().flat_map(char::to_lowercase);
+                chars.iter().all(|c| haystack.any(|h| h == *c))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> Vec<String> {
+        [
+            "18.19.0",
+            "20.10.0",
+            "20.11.0",
+            "21.0.0-rc.1",
+            "20.9.0",
+            "22.1.0",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_search_modes() {
+        let all = versions();
+        let query = |search: &str, mode: SearchMode| VersionQuery {
+            search: Some(search.to_string()),
+            mode,
+            ..Default::default()
+        };
+        let (_, page, _) = search_versions(&all, &query("20.1", SearchMode::Substring)).unwrap();
+        assert_eq!(page, vec!["20.10.0", "20.11.0"]);
+        let (_, page, _) = search_versions(&all, &query(r"^2[02]\.", SearchMode::Regex)).unwrap();
+        assert_eq!(page, vec!["20.9.0", "20.10.0", "20.11.0", "22.1.0"]);
+        let (_, page, _) = search_versions(&all, &query("2110", SearchMode::Fuzzy)).unwrap();
+        assert_eq!(page, vec!["20.11.0"]);
+        assert!(search_versions(&all, &query("(", SearchMode::Regex)).is_err());
+    }
+
+    #[test]
+    fn test_pagination_and_groups() {
+        let query = VersionQuery {
+            include_prerelease: true,
+            group_by_major: true,
+            order: SortOrder::Descending,
+            offset: 1,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let (total, page, groups) = search_versions(&versions(), &query).unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(page, vec!["21.0.0-rc.1", "20.11.0"]);
+        let majors: Vec<(&str, usize)> =
+            groups.iter().map(|g| (g.major.as_str(), g.count)).collect();
+        assert_eq!(majors, vec![("22", 1), ("21", 1), ("20", 3), ("18", 1)]);
+        assert_eq!(groups[2].newest, "20.11.0");
+    }
+}
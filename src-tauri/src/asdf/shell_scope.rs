@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::asdf::resolution::env_var_name;
+use crate::types::{ShellOverride, ShellSyntax};
+
+/// Versions per tool, keyed by project directory.
+type Overrides = BTreeMap<PathBuf, BTreeMap<String, String>>;
+
+/// `ASDF_<TOOL>_VERSION` overrides set with the Shell scope. Like the
+/// variables of a terminal session they live only as long as the app.
+static SHELL_OVERRIDES: OnceLock<Mutex<Overrides>> = OnceLock::new();
+
+fn shell_overrides() -> &'static Mutex<Overrides> {
+    SHELL_OVERRIDES.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+fn project_key(project: &str) -> PathBuf {
+    std::fs::canonicalize(project).unwrap_or_else(|_| PathBuf::from(project))
+}
+
+/// Override `tool` for `project` and everything below it.
+pub fn set(project: &str, tool: &str, versions: &[&str]) {
+    if let Ok(mut overrides) = shell_overrides().lock() {
+        overrides
+            .entry(project_key(project))
+            .or_default()
+            .insert(tool.to_string(), versions.join(" "));
+    }
+}
+
+/// Drop `project`'s override for `tool`, or all of them. Returns whether
+/// anything was removed.
+pub fn unset(project: &str, tool: Option<&str>) -> bool {
+    let Ok(mut overrides) = shell_overrides().lock() else {
+        return false;
+    };
+    let key = project_key(project);
+    let removed = match tool {
+        Some(tool) => overrides
+            .get_mut(&key)
+            .is_some_and(|tools| tools.remove(tool).is_some()),
+        None => overrides.remove(&key).is_some(),
+    };
+    overrides.retain(|_, tools| !tools.is_empty());
+    removed
+}
+
+/// Every stored override, or only those set on `project`.
+pub fn list(project: Option<&str>) -> Vec<ShellOverride> {
+    let Ok(overrides) = shell_overrides().lock() else {
+        return Vec::new();
+    };
+    let key = project.map(project_key);
+    overrides
+        .iter()
+        .filter(|(dir, _)| key.as_ref().is_none_or(|k| k == *dir))
+        .flat_map(|(dir, tools)| {
+            tools.iter().map(|(tool, version)| ShellOverride {
+                project: dir.to_string_lossy().to_string(),
+                tool: tool.clone(),
+                env_var: env_var_name(tool),
+                version: version.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Versions per tool in effect for `dir`. Overrides set on any enclosing
+/// project apply; the nearest project wins.
+pub fn overrides_for(dir: &str) -> BTreeMap<String, String> {
+    let Ok(overrides) = shell_overrides().lock() else {
+        return BTreeMap::new();
+    };
+    resolve(&overrides, &project_key(dir))
+}
+
+/// Env vars in effect for an asdf call in `dir`.
+pub fn env_for(dir: &str) -> BTreeMap<String, String> {
+    overrides_for(dir)
+        .into_iter()
+        .map(|(tool, version)| (env_var_name(&tool), version))
+        .collect()
+}
+
+fn resolve(overrides: &Overrides, dir: &Path) -> BTreeMap<String, String> {
+    // Ancestors sort before their descendants, so nearer projects
+    // overwrite outer ones.
+    overrides
+        .iter()
+        .filter(|(project, _)| dir.starts_with(project))
+        .flat_map(|(_, tools)| tools.clone())
+        .collect()
+}
+
+/// Lines that set `overrides` in a terminal.
+pub fn render(overrides: &[ShellOverride], syntax: ShellSyntax) -> String {
+    overrides
+        .iter()
+        .map(|o| {
+            let value = format!("'{}'", o.version.replace('\'', r"'\''"));
+            match syntax {
+                ShellSyntax::Posix => format!("export {}={value}\n", o.env_var),
+                ShellSyntax::Fish => format!("set -gx {} {value}\n", o.env_var),
+                ShellSyntax::PowerShell => {
+                    format!("$env:{} = '{}'\n", o.env_var, o.version.replace('\'', "''"))
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_nearest_project_wins() {
+        let mut overrides = Overrides::new();
+        overrides.insert(
+            PathBuf::from("/work"),
+            BTreeMap::from([
+                ("nodejs".to_string(), "18.19.0".to_string()),
+                ("python".to_string(), "3.12.1".to_string()),
+            ]),
+        );
+        overrides.insert(
+            PathBuf::from("/work/app"),
+            BTreeMap::from([("nodejs".to_string(), "20.11.0".to_string())]),
+        );
+        overrides.insert(
+            PathBuf::from("/work/application"),
+            BTreeMap::from([("nodejs".to_string(), "21.7.3".to_string())]),
+        );
+
+        let env = resolve(&overrides, Path::new("/work/app/src"));
+        assert_eq!(env["nodejs"], "20.11.0");
+        assert_eq!(env["python"], "3.12.1");
+        assert!(resolve(&overrides, Path::new("/home")).is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let overrides = [ShellOverride {
+            project: "/work".to_string(),
+            tool: "golang-ci-lint".to_string(),
+            env_var: "ASDF_GOLANG_CI_LINT_VERSION".to_string(),
+            version: "1.55.2".to_string(),
+        }];
+        assert_eq!(
+            render(&overrides, ShellSyntax::Posix),
+            "export ASDF_GOLANG_CI_LINT_VERSION='1.55.2'\n"
+        );
+        assert_eq!(
+            render(&overrides, ShellSyntax::Fish),
+            "set -gx ASDF_GOLANG_CI_LINT_VERSION '1.55.2'\n"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::cmp::Ordering;
+
+use crate::asdf::versioning::{compare_versions, is_prerelease, same_line};
+use crate::types::UpgradePolicy;
+
+/// Newest version in `available` newer than `current` and within the
+/// policy's range: same minor line for `Patch`, same major for `Minor`,
+/// anything for `Major`. Pre-releases are only offered when `current` is
+/// one already.
+pub fn newest_within(
+    current: &str,
+    available: &[String],
+    policy: &UpgradePolicy,
+) -> Option<String> {
+    let depth = match policy {
+        UpgradePolicy::Patch => 2,
+        UpgradePolicy::Minor => 1,
+        UpgradePolicy::Major => 0,
+    };
+    let allow_prerelease = is_prerelease(current);
+    available
+        .iter()
+        .filter(|v| allow_prerelease || !is_prerelease(v))
+        .filter(|v| same_line(current, v, depth))
+        .filter(|v| compare_versions(v, current) == Ordering::Greater)
+        .max_by(|a, b| compare_versions(a, b))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn available() -> Vec<String> {
+        [
+            "18.19.0",
+            "20.11.0",
+            "20.11.1",
+            "20.12.2",
+            "21.0.0-rc.1",
+            "22.1.0",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_newest_within_policy() {
+        let all = available();
+        assert_eq!(
+            newest_within("20.11.0", &all, &UpgradePolicy::Patch),
+            Some("20.11.1".to_string())
+        );
+        assert_eq!(
+            newest_within("20.11.0", &all, &UpgradePolicy::Minor),
+            Some("20.12.2".to_string())
+        );
+        assert_eq!(
+            newest_within("20.11.0", &all, &UpgradePolicy::Major),
+            Some("22.1.0".to_string())
+        );
+        assert_eq!(newest_within("22.1.0", &all, &UpgradePolicy::Major), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+
+/// One run of digits or non-digits within a version string.
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Num(u64),
+    Text(&'a str),
+}
+
+fn tokenize(version: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut numeric = false;
+    for (i, c) in version.char_indices() {
+        let separator = matches!(c, '.' | '-' | '_' | '+');
+        let digit = c.is_ascii_digit();
+        if let Some(s) = start
+            && (separator || digit != numeric)
+        {
+            tokens.push(make_token(&version[s..i], numeric));
+            start = None;
+        }
+        if !separator && start.is_none() {
+            start = Some(i);
+            numeric = digit;
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(make_token(&version[s..], numeric));
+    }
+    tokens
+}
+
+fn make_token(s: &str, numeric: bool) -> Token<'_> {
+    if numeric {
+        Token::Num(s.parse().unwrap_or(u64::MAX))
+    } else {
+        Token::Text(s)
+    }
+}
+
+/// Compare two version strings the way a human would: numeric runs compare
+/// numerically, and a pre-release suffix (`rc1`, `-beta`) sorts before the
+/// release it precedes.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let ta = tokenize(a);
+    let tb = tokenize(b);
+    for (x, y) in ta.iter().zip(tb.iter()) {
+        let ord = match (x, y) {
+            (Token::Num(x), Token::Num(y)) => x.cmp(y),
+            (Token::Text(x), Token::Text(y)) => x.cmp(y),
+            // `1.0.1` is newer than `1.0rc1`
+            (Token::Num(_), Token::Text(_)) => Ordering::Greater,
+            (Token::Text(_), Token::Num(_)) => Ordering::Less,
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    match ta.len().cmp(&tb.len()) {
+        Ordering::Equal => Ordering::Equal,
+        // Trailing text marks a pre-release, trailing numbers a later build.
+        Ordering::Greater => match ta[tb.len()] {
+            Token::Num(_) => Ordering::Greater,
+            Token::Text(_) => Ordering::Less,
+        },
+        Ordering::Less => match tb[ta.len()] {
+            Token::Num(_) => Ordering::Less,
+            Token::Text(_) => Ordering::Greater,
+        },
+    }
+}
+
+/// Whether a version looks like a pre-release (`3.13.0rc1`, `22.0.0-nightly`).
+/// Text before the first number is a distribution prefix, not a pre-release
+/// marker (`temurin-21.0.1+12`).
+pub fn is_prerelease(version: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "a", "alpha", "b", "beta", "rc", "pre", "preview", "dev", "snapshot", "nightly", "ea",
+        "canary", "next",
+    ];
+    tokenize(version)
+        .iter()
+        .skip_while(|t| matches!(t, Token::Text(_)))
+        .any(|t| match t {
+            Token::Text(s) => MARKERS.contains(&s.to_ascii_lowercase().as_str()),
+            Token::Num(_) => false,
+        })
+}
+
+/// Sort versions oldest to newest.
+pub fn sort_versions(versions: &mut [String]) {
+    versions.sort_by(|a, b| compare_versions(a, b));
+}
+
+/// The major release line of a version: its distribution prefix and first
+/// number (`20` for `20.11.0`, `temurin-21` for `temurin-21.0.1+12`).
+/// Versions without a number are their own line.
+pub fn major_line(version: &str) -> String {
+    match version.find(|c: char| c.is_ascii_digit()) {
+        Some(start) => {
+            let end = version[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(version.len(), |i| start + i);
+            version[..end].to_string()
+        }
+        None => version.to_string(),
+    }
+}
+
+/// Whether `a` and `b` share a distribution prefix and their first `depth`
+/// numeric components: depth 1 means the same major line, 2 the same minor.
+pub fn same_line(a: &str, b: &str, depth: usize) -> bool {
+    let (ta, tb) = (tokenize(a), tokenize(b));
+    let prefix_len = |t: &[Token]| t.iter().take_while(|t| matches!(t, Token::Text(_))).count();
+    let (la, lb) = (prefix_len(&ta), prefix_len(&tb));
+    if ta[..la] != tb[..lb] {
+        return false;
+    }
+    let leading = |t: &[Token]| -> Vec<u64> {
+        t.iter()
+            .map_while(|t| match t {
+                Token::Num(n) => Some(*n),
+                Token::Text(_) => None,
+            })
+            .take(depth)
+            .collect()
+    };
+    let (na, nb) = (leading(&ta[la..]), leading(&tb[lb..]));
+    na.len() == depth && na == nb
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_numeric_segments() {
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("20.11.0", "20.11.0"), Ordering::Equal);
+        assert_eq!(compare_versions("3.12", "3.12.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_compare_prerelease() {
+        assert_eq!(compare_versions("3.13.0rc1", "3.13.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-beta", "1.0.0"), Ordering::Less);
+        assert_eq!(
+            compare_versions("1.0.0-beta", "1.0.0-alpha"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn test_is_prerelease() {
+        assert!(is_prerelease("3.13.0rc1"));
+        assert!(is_prerelease("22.0.0-nightly20240101"));
+        assert!(!is_prerelease("20.11.0"));
+        assert!(!is_prerelease("temurin-21.0.1+12"));
+    }
+
+    #[test]
+    fn test_sort_versions() {
+        let mut versions = vec![
+            "1.10.0".to_string(),
+            "1.2.0".to_string(),
+            "1.2.0rc1".to_string(),
+        ];
+        sort_versions(&mut versions);
+        assert_eq!(versions, vec!["1.2.0rc1", "1.2.0", "1.10.0"]);
+    }
+
+    #[test]
+    fn test_major_line() {
+        assert_eq!(major_line("20.11.0"), "20");
+        assert_eq!(major_line("temurin-21.0.1+12"), "temurin-21");
+        assert_eq!(major_line("system"), "system");
+    }
+
+    #[test]
+    fn test_same_line() {
+        assert!(same_line("20.11.0", "20.12.1", 1));
+        assert!(!same_line("20.11.0", "20.12.1", 2));
+        assert!(same_line("3.12.1", "3.12.4", 2));
+        assert!(!same_line("temurin-21.0.1", "zulu-21.0.1", 1));
+        assert!(same_line("1.0", "2.0", 0));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::asdf::args::{PluginName, VersionString, parse_pairs, with_positionals};
+use crate::asdf::batch::BatchRunner;
+use crate::asdf::executor::{blocking, run_asdf_streaming};
+use crate::asdf::{disk, gc, impact, query, scan};
+use crate::config::app_config;
+use crate::config::context::ConfigContext;
+use crate::error::AppError;
+use crate::types::{BatchEvent, BatchSummary, DiskUsage, DownloadEntry, GcPlan, InstallEvent};
+
+/// Space used by installs, downloads and plugin checkouts, per plugin and
+/// per version.
+#[tauri::command]
+pub async fn disk_usage() -> Result<DiskUsage, AppError> {
+    let data_dir = app_config::asdf_data_dir()?;
+    blocking(move || disk::disk_usage(&data_dir)).await
+}
+
+/// Installed versions that nothing known resolves to: not the home version
+/// file, what asdf resolves in any recent project, or any version file
+/// under `roots`.
+#[tauri::command]
+pub async fn gc_plan(roots: Vec<String>) -> Result<GcPlan, AppError> {
+    let ctx = ConfigContext::load();
+    let plugins = query::plugin_names(ctx.asdf_path.as_deref()).await?;
+    let files = scan::known_version_files(&roots, &plugins).await?;
+    let data_dir = app_config::asdf_data_dir()?;
+    blocking(move || gc::gc_plan(&disk::disk_usage(&data_dir), &files)).await
+}
+
+/// Uninstall the selected `(tool, version)` pairs as tracked jobs. Like
+/// `uninstall`, the global version is skipped unless `force` is set.
+#[tauri::command]
+pub async fn gc_apply(
+    versions: Vec<(String, String)>,
+    force: bool,
+    on_event: tauri::ipc::Channel<BatchEvent>,
+) -> Result<BatchSummary, AppError> {
+    let ctx = ConfigContext::load();
+    let versions = parse_pairs(&versions)?;
+    let home = if force {
+        None
+    } else {
+        scan::home_version_file()
+    };
+    let mut runner = BatchRunner::new(move |event| {
+        let _ = on_event.send(event);
+    });
+    for (tool, version) in &versions {
+        if let Err(e) = impact::ensure_not_global(home.as_ref(), tool.as_str(), version.as_str()) {
+            runner.skip(tool.as_str(), version.as_str(), e.to_string());
+            continue;
+        }
+        runner
+            .uninstall(tool.as_str(), version.as_str(), ctx.asdf_path.as_deref())
+            .await;
+    }
+    Ok(runner.finish())
+}
+
+/// Downloads kept by `--keep-download`, per plugin and version.
+#[tauri::command]
+pub async fn list_downloads() -> Result<Vec<DownloadEntry>, AppError> {
+    let data_dir = app_config::asdf_data_dir()?;
+    blocking(move || disk::list_downloads(&data_dir)).await
+}
+
+/// Delete the selected kept downloads, returning the bytes freed.
+#[tauri::command]
+pub async fn delete_downloads(items: Vec<(String, String)>) -> Result<u64, AppError> {
+    let items = parse_pairs(&items)?;
+    let data_dir = app_config::asdf_data_dir()?;
+    blocking(move || {
+        let mut freed = 0;
+        for (tool, version) in &items {
+            freed += disk::remove_download(&data_dir, tool.as_str(), version.as_str())?;
+        }
+        Ok(freed)
+    })
+    .await?
+}
+
+/// Delete kept downloads whose version is no longer installed, returning
+/// what was removed.
+#[tauri::command]
+pub async fn prune_downloads() -> Result<Vec<DownloadEntry>, AppError> {
+    let data_dir = app_config::asdf_data_dir()?;
+    blocking(move || {
+        let mut removed = Vec::new();
+        for entry in disk::list_downloads(&data_dir) {
+            if !entry.installed {
+                disk::remove_download(&data_dir, &entry.tool, &entry.version)?;
+                removed.push(entry);
+            }
+        }
+        Ok(removed)
+    })
+    .await?
+}
+
+/// Reinstall a version with its kept download in place. Only plugins with
+/// a separate `bin/download` step are accepted. asdf still runs that step,
+/// and whether it reuses the files or fetches them again is up to the
+/// plugin, so the network may be used anyway. The previous install is
+/// restored if the reinstall fails, and the global version is refused
+/// unless `force` is set.
+#[tauri::command]
+pub async fn reinstall_from_download(
+    name: String,
+    version: String,
+    force: bool,
+    on_output: tauri::ipc::Channel<InstallEvent>,
+) -> Result<(), AppError> {
+    let ctx = ConfigContext::load();
+    let name = PluginName::parse(name)?;
+    let version = VersionString::parse(version)?;
+    if !force {
+        impact::ensure_not_global(
+            scan::home_version_file().as_ref(),
+            name.as_str(),
+            version.as_str(),
+        )?;
+    }
+    let data_dir = app_config::asdf_data_dir()?;
+    let dir_name = disk::install_dir_name(version.as_str());
+
+    let download = disk::version_dir(&data_dir, "downloads", name.as_str(), &dir_name)?;
+    let probe = download.clone();
+    if blocking(move || disk::tree_stats(&probe).bytes).await? == 0 {
+        return Err(AppError::ConfigError(format!(
+            "no kept download for {} {}",
+            name.as_str(),
+            version.as_str()
+        )));
+    }
+    let download_script = data_dir
+        .join("plugins")
+        .join(name.as_str())
+        .join("bin")
+        .join("download");
+    if !download_script.exists() {
+        return Err(AppError::ConfigError(format!(
+            "plugin {} downloads during install and cannot reuse a kept download",
+            name.as_str()
+        )));
+    }
+
+    // Move the install aside rather than `asdf uninstall`, which would also
+    // delete the download we are about to reuse. Installing reshims
+    // afterwards.
+    let install = disk::version_dir(&data_dir, "installs", name.as_str(), &dir_name)?;
+    let backup = disk::version_dir(&data_dir, disk::REINSTALL_BACKUPS, name.as_str(), &dir_name)?;
+    let backed_up = install.exists();
+    if backed_up {
+        if backup.exists() {
+            std::fs::remove_dir_all(&backup)?;
+        }
+        if let Some(parent) = backup.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&install, &backup)?;
+    }
+    let result = run_asdf_streaming(
+        &with_positionals(
+            &["install", "--keep-download"],
+            &[name.as_str(), version.as_str()],
+        ),
+        ctx.cwd.as_deref(),
+        ctx.asdf_path.as_deref(),
+        move |event| {
+            let _ = on_output.send(event);
+        },
+    )
+    .await;
+    if backed_up {
+        match result {
+            Ok(()) => std::fs::remove_dir_all(&backup)?,
+            Err(_) => {
+                if install.exists() {
+                    std::fs::remove_dir_all(&install)?;
+                }
+                std::fs::rename(&backup, &install)?;
+            }
+        }
+    }
+    result
+}
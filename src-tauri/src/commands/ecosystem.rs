@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use crate::asdf::args::with_positionals;
+use crate::asdf::executor::run_asdf;
+use crate::asdf::import::{self, VersionFilter};
+use crate::asdf::{export, parser};
+use crate::config::context::ConfigContext;
+use crate::error::AppError;
+use crate::path_guard;
+use crate::types::{
+    ExportResult, ExportStatus, ExportTarget, ToolSuggestion, ToolVersion, ToolVersionsSuggestion,
+};
+
+/// Generate or update other ecosystems' version files next to the
+/// `.tool-versions` at `path`. Targets whose existing file disagrees are
+/// reported as conflicts and left alone unless `overwrite` is set.
+/// With `dry_run` nothing is written.
+#[tauri::command]
+pub async fn export_tool_versions(
+    path: String,
+    targets: Vec<ExportTarget>,
+    overwrite: bool,
+    dry_run: bool,
+) -> Result<Vec<ExportResult>, AppError> {
+    let source = path_guard::check_version_file(&path)?;
+    let entries = parser::parse_tool_versions(&std::fs::read_to_string(&source)?);
+    let dir = source
+        .parent()
+        .ok_or_else(|| AppError::ConfigError(format!("no parent directory for {path}")))?;
+
+    let mut results = Vec::new();
+    for target in targets {
+        let name = export::file_name(&target);
+        let target_path = path_guard::check_named_file(&dir.join(name), name)?;
+        let existing = std::fs::read_to_string(&target_path).ok();
+        let rendered = export::render(&target, &entries, existing.as_deref())?;
+
+        let Some(rendered) = rendered else {
+            results.push(ExportResult {
+                target,
+                path: target_path.to_string_lossy().to_string(),
+                status: ExportStatus::Skipped,
+                conflicts: Vec::new(),
+                content: None,
+            });
+            continue;
+        };
+
+        let status = match existing {
+            None => ExportStatus::Created,
+            Some(ref e) if *e == rendered.content => ExportStatus::Unchanged,
+            Some(_) if !rendered.conflicts.is_empty() && !overwrite => ExportStatus::Conflict,
+            Some(_) => ExportStatus::Updated,
+        };
+        if !dry_run && matches!(status, ExportStatus::Created | ExportStatus::Updated) {
+            std::fs::write(&target_path, &rendered.content)?;
+        }
+        results.push(ExportResult {
+            target,
+            path: target_path.to_string_lossy().to_string(),
+            status,
+            conflicts: rendered.conflicts,
+            content: Some(rendered.content),
+        });
+    }
+    Ok(results)
+}
+
+/// Propose a `.tool-versions` for `dir` from the ecosystem manifests it
+/// contains. The first manifest found for each tool is selected and its
+/// constraint resolved with `asdf latest`; nothing is written.
+#[tauri::command]
+pub async fn suggest_tool_versions(dir: String) -> Result<ToolVersionsSuggestion, AppError> {
+    let ctx = ConfigContext::load();
+    let mut suggestions: Vec<ToolSuggestion> = Vec::new();
+    let mut entries: Vec<ToolVersion> = Vec::new();
+
+    for pin in import::detect_pins(Path::new(&dir)) {
+        if let Some(winner) = suggestions
+            .iter()
+            .find(|s| s.selected && s.tool == pin.tool)
+        {
+            let note = format!("superseded by {}", winner.source);
+            suggestions.push(ToolSuggestion {
+                tool: pin.tool.to_string(),
+                source: pin.source,
+                constraint: pin.constraint,
+                resolved: None,
+                selected: false,
+                note: Some(note),
+            });
+            continue;
+        }
+
+        let (resolved, note) = match import::constraint_filter(&pin.constraint) {
+            Some(VersionFilter::Exact(v)) => (Some(v), None),
+            Some(filter) => {
+                let mut positionals = vec![pin.tool];
+                if let VersionFilter::Prefix(ref p) = filter {
+                    positionals.push(p.as_str());
+                }
+                let args = with_positionals(&["latest"], &positionals);
+                match run_asdf(&args, None, ctx.asdf_path.as_deref()).await {
+                    Ok(output) => (Some(output.stdout.trim().to_string()), None),
+                    Err(e) => (None, Some(e.to_string())),
+                }
+            }
+            None => (
+                None,
+                Some("constraint cannot be resolved by asdf".to_string()),
+            ),
+        };
+
+        if let Some(ref version) = resolved {
+            entries.push(ToolVersion {
+                tool: pin.tool.to_string(),
+                versions: vec![version.clone()],
+            });
+        }
+        suggestions.push(ToolSuggestion {
+            tool: pin.tool.to_string(),
+            source: pin.source,
+            constraint: pin.constraint,
+            selected: resolved.is_some(),
+            resolved,
+            note,
+        });
+    }
+
+    let content = parser::format_tool_versions(&entries);
+    Ok(ToolVersionsSuggestion {
+        suggestions,
+        entries,
+        content,
+    })
+}
@@ -0,0 +1,36 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::asdf::{eol, query, releases, scan};
+use crate::config::context::ConfigContext;
+use crate::error::AppError;
+use crate::types::{EolReport, ReportFormat};
+
+/// Runtimes pinned by the home version file, recent projects or any
+/// version file under `roots` that are past end-of-life or reach it within
+/// `within_days`.
+#[tauri::command]
+pub async fn eol_report(roots: Vec<String>, within_days: u32) -> Result<EolReport, AppError> {
+    let ctx = ConfigContext::load();
+    let plugins = query::plugin_names(ctx.asdf_path.as_deref()).await?;
+    let files = scan::known_version_files(&roots, &plugins).await?;
+    let today = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / 86_400;
+    Ok(eol::eol_report(
+        &files,
+        &releases::load(),
+        today as i64,
+        within_days,
+    ))
+}
+
+/// Render a report as Markdown or JSON for sharing.
+#[tauri::command]
+pub async fn export_eol_report(
+    report: EolReport,
+    format: ReportFormat,
+) -> Result<String, AppError> {
+    eol::render(&report, &format)
+}
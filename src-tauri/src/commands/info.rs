@@ -1,3 +1,4 @@
+use crate::asdf::args::{check_arg, with_positionals};
 use crate::asdf::executor::run_asdf;
 use crate::asdf::parser;
 use crate::config::context::ConfigContext;
@@ -21,8 +22,9 @@ pub async fn asdf_info() -> Result<AsdfInfo, AppError> {
 #[tauri::command]
 pub async fn asdf_env(command: String) -> Result<Vec<EnvVar>, AppError> {
     let ctx = ConfigContext::load();
+    check_arg("command", &command)?;
     let output = run_asdf(
-        &["env", &command],
+        &with_positionals(&["env"], &[&command]),
         ctx.cwd.as_deref(),
         ctx.asdf_path.as_deref(),
     )
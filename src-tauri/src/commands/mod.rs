@@ -1,3 +1,6 @@
+pub mod disk;
+pub mod ecosystem;
+pub mod eol;
 pub mod info;
 pub mod plugin;
 pub mod settings;
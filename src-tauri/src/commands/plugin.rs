@@ -1,8 +1,10 @@
+use crate::asdf::args::{PluginName, check_arg, with_positionals};
 use crate::asdf::executor::run_asdf;
-use crate::asdf::parser;
+use crate::asdf::{default_packages, parser, plugin_git, query};
+use crate::config::app_config;
 use crate::config::context::ConfigContext;
 use crate::error::AppError;
-use crate::types::{Plugin, PluginRegistry};
+use crate::types::{DefaultPackagesFile, Plugin, PluginDetails, PluginDrift, PluginRegistry};
 
 #[tauri::command]
 pub async fn plugin_list(urls: bool, refs: bool) -> Result<Vec<Plugin>, AppError> {
@@ -28,10 +30,13 @@ pub async fn plugin_list_all() -> Result<Vec<PluginRegistry>, AppError> {
 #[tauri::command]
 pub async fn plugin_add(name: String, git_url: Option<String>) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let mut args = vec!["plugin", "add", name.as_str()];
+    let name = PluginName::parse(name)?;
+    let mut positionals = vec![name.as_str()];
     if let Some(ref url) = git_url {
-        args.push(url.as_str());
+        check_arg("git url", url)?;
+        positionals.push(url.as_str());
     }
+    let args = with_positionals(&["plugin", "add"], &positionals);
     let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
     Ok(output.stdout.trim().to_string())
 }
@@ -39,22 +44,118 @@ pub async fn plugin_add(name: String, git_url: Option<String>) -> Result<String,
 #[tauri::command]
 pub async fn plugin_remove(name: String) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let output = run_asdf(&["plugin", "remove", &name], None, ctx.asdf_path.as_deref()).await?;
+    let name = PluginName::parse(name)?;
+    let args = with_positionals(&["plugin", "remove"], &[name.as_str()]);
+    let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
     Ok(output.stdout.trim().to_string())
 }
 
+/// Update one plugin or all of them. Updating one plugin to `git_ref`
+/// also records the ref as its pin.
 #[tauri::command]
-pub async fn plugin_update(name: Option<String>, all: bool) -> Result<String, AppError> {
+pub async fn plugin_update(
+    name: Option<String>,
+    all: bool,
+    git_ref: Option<String>,
+) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let args = if all {
-        vec!["plugin", "update", "--all"]
-    } else if let Some(ref n) = name {
-        vec!["plugin", "update", n.as_str()]
-    } else {
-        return Err(AppError::ParseError(
-            "either name or --all must be specified".to_string(),
-        ));
+    let name = name.map(PluginName::parse).transpose()?;
+    if let Some(ref r) = git_ref {
+        check_arg("git ref", r)?;
+    }
+    let args = match (all, &name, &git_ref) {
+        (true, _, Some(_)) => {
+            return Err(AppError::ParseError(
+                "a git ref can only be used when updating a single plugin".to_string(),
+            ));
+        }
+        (true, _, None) => vec!["plugin", "update", "--all"],
+        (false, Some(n), Some(r)) => with_positionals(&["plugin", "update"], &[n.as_str(), r]),
+        (false, Some(n), None) => with_positionals(&["plugin", "update"], &[n.as_str()]),
+        (false, None, _) => {
+            return Err(AppError::ParseError(
+                "either name or --all must be specified".to_string(),
+            ));
+        }
     };
     let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
+    if let (Some(n), Some(r)) = (name, git_ref) {
+        set_pin(n.as_str(), Some(r))?;
+    }
     Ok(output.stdout.trim().to_string())
 }
+
+fn set_pin(name: &str, git_ref: Option<String>) -> Result<(), AppError> {
+    let mut config = app_config::read_config()?;
+    match git_ref {
+        Some(r) => config.plugin_pins.insert(name.to_string(), r),
+        None => config.plugin_pins.remove(name),
+    };
+    app_config::write_config(&config)
+}
+
+/// Record (or with `None`, clear) the ref a plugin should be at, without
+/// updating it.
+#[tauri::command]
+pub async fn set_plugin_pin(name: String, git_ref: Option<String>) -> Result<(), AppError> {
+    let name = PluginName::parse(name)?;
+    if let Some(ref r) = git_ref {
+        check_arg("git ref", r)?;
+    }
+    set_pin(name.as_str(), git_ref)
+}
+
+/// Each installed or pinned plugin's HEAD compared with its pin, read from
+/// the local checkouts.
+#[tauri::command]
+pub async fn plugin_drift() -> Result<Vec<PluginDrift>, AppError> {
+    let ctx = ConfigContext::load();
+    let pins = app_config::read_config()?.plugin_pins;
+    let data_dir = app_config::asdf_data_dir()?;
+    let mut names = query::plugin_names(ctx.asdf_path.as_deref()).await?;
+    names.extend(pins.keys().cloned());
+    names.sort();
+    names.dedup();
+
+    let mut drift = Vec::with_capacity(names.len());
+    for name in &names {
+        let dir = plugin_git::plugin_dir(&data_dir, name);
+        let dir = dir.is_dir().then_some(dir.as_path());
+        drift.push(plugin_git::drift(name, dir, pins.get(name).map(String::as_str)).await);
+    }
+    Ok(drift)
+}
+
+/// Git state, callbacks and README of an installed plugin, read from its
+/// local checkout.
+#[tauri::command]
+pub async fn plugin_details(name: String) -> Result<PluginDetails, AppError> {
+    let name = PluginName::parse(name)?;
+    plugin_git::details(&app_config::asdf_data_dir()?, name.as_str()).await
+}
+
+/// Default-packages files read by the installed plugins that support one.
+#[tauri::command]
+pub async fn default_packages_files() -> Result<Vec<DefaultPackagesFile>, AppError> {
+    let ctx = ConfigContext::load();
+    query::plugin_names(ctx.asdf_path.as_deref())
+        .await?
+        .iter()
+        .filter(|name| default_packages::supports(name))
+        .map(|name| default_packages::read(name))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn read_default_packages(plugin: String) -> Result<DefaultPackagesFile, AppError> {
+    default_packages::read(&plugin)
+}
+
+/// Replace the package list, keeping the file's comments.
+#[tauri::command]
+pub async fn write_default_packages(
+    plugin: String,
+    packages: Vec<String>,
+) -> Result<DefaultPackagesFile, AppError> {
+    default_packages::write(&plugin, &packages)
+}
@@ -1,10 +1,12 @@
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::asdf::releases;
 use crate::config::app_config;
 use crate::config::app_config::{AppConfig, RecentProject};
 use crate::config::context::MAX_RECENT_PROJECTS;
 use crate::error::AppError;
+use crate::watcher;
 
 #[tauri::command]
 pub async fn read_config() -> Result<AppConfig, AppError> {
@@ -13,11 +15,15 @@ pub async fn read_config() -> Result<AppConfig, AppError> {
 
 #[tauri::command]
 pub async fn write_config(config: AppConfig) -> Result<(), AppError> {
-    app_config::write_config(&config)
+    let current = app_config::read_config()?;
+    app_config::write_config(&app_config::merge_frontend_config(config, &current))
 }
 
 #[tauri::command]
-pub async fn set_working_directory(path: String) -> Result<AppConfig, AppError> {
+pub async fn set_working_directory(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<AppConfig, AppError> {
     let mut config = app_config::read_config()?;
     config.working_directory = Some(path.clone());
 
@@ -45,6 +51,7 @@ pub async fn set_working_directory(path: String) -> Result<AppConfig, AppError>
     config.recent_projects.truncate(MAX_RECENT_PROJECTS);
 
     app_config::write_config(&config)?;
+    watcher::refresh(&app);
     Ok(config)
 }
 
@@ -52,3 +59,17 @@ pub async fn set_working_directory(path: String) -> Result<AppConfig, AppError>
 pub async fn read_asdfrc() -> Result<Vec<(String, String)>, AppError> {
     app_config::read_asdfrc()
 }
+
+/// Why the release metadata override file is being ignored, if it is.
+#[tauri::command]
+pub async fn release_metadata_error() -> Result<Option<String>, AppError> {
+    Ok(releases::override_error())
+}
+
+/// Why live updates are off, if the last watcher refresh failed.
+#[tauri::command]
+pub async fn watcher_error(
+    state: tauri::State<'_, watcher::WatcherState>,
+) -> Result<Option<String>, AppError> {
+    Ok(state.error())
+}
@@ -1,3 +1,4 @@
+use crate::asdf::args::{PluginName, VersionString, check_arg, with_positionals};
 use crate::asdf::executor::run_asdf;
 use crate::asdf::parser;
 use crate::config::context::ConfigContext;
@@ -7,8 +8,9 @@ use crate::types::ShimVersion;
 #[tauri::command]
 pub async fn which_command(command: String) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
+    check_arg("command", &command)?;
     let output = run_asdf(
-        &["which", &command],
+        &with_positionals(&["which"], &[&command]),
         ctx.cwd.as_deref(),
         ctx.asdf_path.as_deref(),
     )
@@ -19,13 +21,18 @@ pub async fn which_command(command: String) -> Result<String, AppError> {
 #[tauri::command]
 pub async fn shim_versions(command: String) -> Result<Vec<ShimVersion>, AppError> {
     let ctx = ConfigContext::load();
-    let output = run_asdf(&["shimversions", &command], None, ctx.asdf_path.as_deref()).await?;
+    check_arg("command", &command)?;
+    let args = with_positionals(&["shimversions"], &[&command]);
+    let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
     Ok(parser::parse_shim_versions(&output.stdout))
 }
 
 #[tauri::command]
 pub async fn reshim(name: String, version: String) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let output = run_asdf(&["reshim", &name, &version], None, ctx.asdf_path.as_deref()).await?;
+    let name = PluginName::parse(name)?;
+    let version = VersionString::parse(version)?;
+    let args = with_positionals(&["reshim"], &[name.as_str(), version.as_str()]);
+    let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
     Ok(output.stdout.trim().to_string())
 }
@@ -1,68 +1,206 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use crate::asdf::parser;
+use crate::asdf::args::parse_pairs;
+use crate::asdf::batch::BatchRunner;
+use crate::asdf::executor::blocking;
+use crate::asdf::lint::{self, LintContext};
+use crate::asdf::{cache, merge, parser, query, resolution, scan, upgrade};
+use crate::config::app_config;
 use crate::config::context::ConfigContext;
 use crate::error::AppError;
-use crate::types::{SetScope, ToolVersion};
+use crate::path_guard;
+use crate::types::{
+    BatchEvent, BatchSummary, LintDiagnostic, MergeStrategy, SetScope, ToolVersion,
+    ToolVersionsDiff, ToolVersionsScan, UpgradeCandidate, UpgradePlan, UpgradePolicy,
+};
 
 #[tauri::command]
 pub async fn read_tool_versions(path: String) -> Result<Vec<ToolVersion>, AppError> {
-    let content = std::fs::read_to_string(&path)?;
-    Ok(parser::parse_tool_versions(&content))
+    read_entries(&path_guard::check_version_file(&path)?)
 }
 
 #[tauri::command]
 pub async fn write_tool_versions(path: String, entries: Vec<ToolVersion>) -> Result<(), AppError> {
-    let content = entries
-        .iter()
-        .map(|e| format!("{} {}", e.tool, e.versions.join(" ")))
-        .collect::<Vec<_>>()
-        .join("\n");
-    let content = if content.is_empty() {
-        content
-    } else {
-        format!("{content}\n")
-    };
-    std::fs::write(&path, content)?;
+    write_entries(&path_guard::check_version_file(&path)?, &entries)
+}
+
+fn read_entries(path: &Path) -> Result<Vec<ToolVersion>, AppError> {
+    Ok(parser::parse_tool_versions(&std::fs::read_to_string(path)?))
+}
+
+fn write_entries(path: &Path, entries: &[ToolVersion]) -> Result<(), AppError> {
+    std::fs::write(path, parser::format_tool_versions(entries))?;
     Ok(())
 }
 
+#[tauri::command]
+pub async fn diff_tool_versions(a: String, b: String) -> Result<ToolVersionsDiff, AppError> {
+    let left = read_entries(&path_guard::check_version_file(&a)?)?;
+    let right = read_entries(&path_guard::check_version_file(&b)?)?;
+    Ok(merge::diff_tool_versions(&left, &right))
+}
+
+/// Merge `a` and `b` and write the result to `output`.
+#[tauri::command]
+pub async fn merge_tool_versions(
+    a: String,
+    b: String,
+    strategy: MergeStrategy,
+    output: String,
+) -> Result<Vec<ToolVersion>, AppError> {
+    let left = read_entries(&path_guard::check_version_file(&a)?)?;
+    let right = read_entries(&path_guard::check_version_file(&b)?)?;
+    let merged = merge::merge_tool_versions(&left, &right, &strategy);
+    write_entries(&path_guard::check_version_file(&output)?, &merged)?;
+    Ok(merged)
+}
+
+#[tauri::command]
+pub async fn lint_tool_versions(path: String) -> Result<Vec<LintDiagnostic>, AppError> {
+    let ctx = ConfigContext::load();
+    let content = std::fs::read_to_string(path_guard::check_version_file(&path)?)?;
+
+    let plugins = query::plugin_names(ctx.asdf_path.as_deref()).await?;
+
+    let mut installed = HashMap::new();
+    let mut available = HashMap::new();
+    for entry in parser::parse_tool_versions(&content) {
+        if !plugins.contains(&entry.tool) || installed.contains_key(&entry.tool) {
+            continue;
+        }
+        let versions = query::installed_versions(&entry.tool, ctx.asdf_path.as_deref()).await;
+        if let Some(all) = cache::cached_list_all(&entry.tool) {
+            available.insert(entry.tool.clone(), all);
+        }
+        installed.insert(entry.tool, versions);
+    }
+
+    let lint_ctx = LintContext {
+        plugins,
+        installed,
+        available,
+    };
+    Ok(lint::lint_tool_versions(&content, &lint_ctx))
+}
+
+#[tauri::command]
+pub async fn scan_tool_versions(
+    root: String,
+    ignore_globs: Vec<String>,
+) -> Result<ToolVersionsScan, AppError> {
+    let filename = app_config::tool_versions_filename();
+    blocking(move || {
+        let files = scan::scan_tool_versions(Path::new(&root), &filename, &ignore_globs)?;
+        let pins = scan::aggregate_pins(&files);
+        Ok(ToolVersionsScan { files, pins })
+    })
+    .await?
+}
+
+/// For every tool pinned in `path`, find the newest available version
+/// allowed by `policy`.
+#[tauri::command]
+pub async fn plan_upgrades(path: String, policy: UpgradePolicy) -> Result<UpgradePlan, AppError> {
+    let ctx = ConfigContext::load();
+    let entries = read_entries(&path_guard::check_version_file(&path)?)?;
+
+    let mut upgrades = Vec::new();
+    for entry in entries {
+        let Some(current) = entry.versions.first() else {
+            continue;
+        };
+        if current == "system" || current.starts_with("ref:") || current.starts_with("path:") {
+            continue;
+        }
+        let available = query::available_versions(&entry.tool, ctx.asdf_path.as_deref())
+            .await
+            .unwrap_or_default();
+        upgrades.push(UpgradeCandidate {
+            target: upgrade::newest_within(current, &available, &policy),
+            current: current.clone(),
+            tool: entry.tool,
+        });
+    }
+
+    Ok(UpgradePlan {
+        path,
+        policy,
+        upgrades,
+    })
+}
+
+/// Rewrite the primary versions in `path` (keeping comments), then
+/// optionally install the new versions as tracked jobs.
+#[tauri::command]
+pub async fn apply_upgrade_plan(
+    path: String,
+    upgrades: Vec<(String, String)>,
+    install: bool,
+    on_event: tauri::ipc::Channel<BatchEvent>,
+) -> Result<Option<BatchSummary>, AppError> {
+    let upgrades = parse_pairs(&upgrades)?;
+    let target = path_guard::check_version_file(&path)?;
+    let content = std::fs::read_to_string(&target)?;
+    std::fs::write(&target, parser::rewrite_tool_versions(&content, &upgrades))?;
+
+    if !install {
+        return Ok(None);
+    }
+    let ctx = ConfigContext::load();
+    let cwd = Path::new(&path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string());
+    let mut runner = BatchRunner::new(move |event| {
+        let _ = on_event.send(event);
+    });
+    for (tool, version) in &upgrades {
+        runner
+            .install(
+                tool.as_str(),
+                version.as_str(),
+                cwd.as_deref(),
+                ctx.asdf_path.as_deref(),
+                false,
+            )
+            .await;
+    }
+    Ok(Some(runner.finish()))
+}
+
 #[tauri::command]
 pub async fn get_tool_versions_path(scope: SetScope) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
+    let filename = app_config::tool_versions_filename();
 
     match scope {
         SetScope::Home => {
             let home = dirs::home_dir().ok_or_else(|| {
                 AppError::ConfigError("cannot determine home directory".to_string())
             })?;
-            Ok(home.join(".tool-versions").to_string_lossy().to_string())
+            Ok(home.join(&filename).to_string_lossy().to_string())
         }
         SetScope::Local => {
             let cwd = ctx.cwd_or_home();
             Ok(PathBuf::from(cwd)
-                .join(".tool-versions")
+                .join(&filename)
                 .to_string_lossy()
                 .to_string())
         }
+        SetScope::Shell => Err(AppError::ConfigError(
+            "the shell scope has no version file".to_string(),
+        )),
         SetScope::Parent => {
             let cwd = ctx.cwd_or_home();
 
-            // Walk up directories to find closest .tool-versions
-            let mut dir = PathBuf::from(&cwd);
-            loop {
-                let candidate = dir.join(".tool-versions");
-                if candidate.exists() {
-                    return Ok(candidate.to_string_lossy().to_string());
-                }
-                if !dir.pop() {
-                    break;
-                }
+            // Walk up directories to find the closest version file
+            if let Some(found) = resolution::nearest_version_file(Path::new(&cwd), &filename) {
+                return Ok(found.to_string_lossy().to_string());
             }
 
             // Fallback to cwd
             Ok(PathBuf::from(cwd)
-                .join(".tool-versions")
+                .join(&filename)
                 .to_string_lossy()
                 .to_string())
         }
@@ -1,18 +1,37 @@
-use crate::asdf::executor::{run_asdf, run_asdf_streaming};
-use crate::asdf::parser;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::asdf::args::{PluginName, VersionString, parse_pairs, with_positionals};
+use crate::asdf::batch::{self, BatchRunner, InstallPlan};
+use crate::asdf::executor::{blocking, run_asdf, run_asdf_streaming};
+use crate::asdf::resolution;
+use crate::asdf::{
+    cache, default_packages, disk, impact, matrix, parser, query, releases, scan, search,
+    shell_scope,
+};
+use crate::config::app_config;
 use crate::config::context::ConfigContext;
+use crate::config::env::user_env_var;
 use crate::error::AppError;
-use crate::types::{CurrentVersion, InstallEvent, LatestInfo, SetScope};
+use crate::types::{
+    BatchEvent, BatchSummary, CurrentVersion, DefaultPackagesFile, InstallEvent, LatestInfo,
+    ProjectInstallPlan, ResolutionExplanation, SetScope, ShellOverride, ShellSyntax,
+    UninstallImpact, VersionEntry, VersionMatrix, VersionPage, VersionQuery,
+};
 
 #[tauri::command]
 pub async fn current(name: Option<String>) -> Result<Vec<CurrentVersion>, AppError> {
     let ctx = ConfigContext::load();
-    let mut args = vec!["current"];
-    if let Some(ref n) = name {
-        args.push(n.as_str());
-    }
+    let name = name.map(PluginName::parse).transpose()?;
+    let positionals: Vec<&str> = name.iter().map(PluginName::as_str).collect();
+    let args = with_positionals(&["current"], &positionals);
     let output = run_asdf(&args, ctx.cwd.as_deref(), ctx.asdf_path.as_deref()).await?;
-    parser::parse_current(&output.stdout)
+    let releases = releases::load();
+    let mut versions = parser::parse_current(&output.stdout)?;
+    for v in &mut versions {
+        v.release = releases::lookup(&releases, &v.name, &v.version).cloned();
+    }
+    Ok(versions)
 }
 
 #[tauri::command]
@@ -26,31 +45,333 @@ pub async fn install(
     let ctx = ConfigContext::load();
     let effective_cwd = cwd.or(ctx.cwd);
 
-    let mut args = vec!["install"];
-    if let Some(ref n) = name {
-        args.push(n.as_str());
-    }
-    if let Some(ref v) = version {
-        args.push(v.as_str());
-    }
-    if keep_download {
-        args.push("--keep-download");
-    }
+    let name = name.map(PluginName::parse).transpose()?;
+    let version = version.map(VersionString::parse).transpose()?;
+    let mut positionals: Vec<&str> = name.iter().map(PluginName::as_str).collect();
+    positionals.extend(version.iter().map(VersionString::as_str));
+    let flags: &[&str] = if keep_download {
+        &["install", "--keep-download"]
+    } else {
+        &["install"]
+    };
+    let args = with_positionals(flags, &positionals);
 
     run_asdf_streaming(
         &args,
         effective_cwd.as_deref(),
         ctx.asdf_path.as_deref(),
-        &on_output,
+        move |event| {
+            let _ = on_output.send(event);
+        },
     )
     .await
 }
 
+/// Effective version of every tool in each recent project and in each
+/// directory holding a version file under the configured scan roots or
+/// `roots`. Roots that cannot be scanned are reported, not fatal.
+#[tauri::command]
+pub async fn version_matrix(roots: Vec<String>) -> Result<VersionMatrix, AppError> {
+    let ctx = ConfigContext::load();
+    let config = app_config::read_config()?;
+    let filename = app_config::tool_versions_filename();
+
+    let mut dirs: Vec<String> = config
+        .recent_projects
+        .iter()
+        .map(|p| p.path.clone())
+        .collect();
+    let scan_roots: Vec<String> = config.scan_roots.into_iter().chain(roots).collect();
+    let scan_filename = filename.clone();
+    let (found, skipped_roots) = blocking(move || {
+        let mut found = Vec::new();
+        let mut skipped = Vec::new();
+        for root in &scan_roots {
+            match scan::scan_tool_versions(Path::new(root), &scan_filename, &[]) {
+                Ok(files) => found.extend(files),
+                Err(e) => skipped.push(e.to_string()),
+            }
+        }
+        (found, skipped)
+    })
+    .await?;
+    for file in found {
+        if let Some(parent) = Path::new(&file.path).parent() {
+            dirs.push(parent.to_string_lossy().to_string());
+        }
+    }
+    let mut seen = HashSet::new();
+    dirs.retain(|d| seen.insert(d.clone()));
+
+    let mut projects = Vec::new();
+    for dir in dirs {
+        let versions = match run_asdf(&["current"], Some(&dir), ctx.asdf_path.as_deref()).await {
+            Ok(output) => parser::parse_current(&output.stdout)?,
+            Err(_) => versions_from_file(&dir, &filename),
+        };
+        projects.push((dir, versions));
+    }
+    Ok(VersionMatrix {
+        skipped_roots,
+        ..matrix::build_matrix(projects)
+    })
+}
+
+/// Fallback when `asdf current` fails in `dir` (for example a project on an
+/// unmounted disk): the first pins of the nearest version file, checked
+/// against the installs directory.
+fn versions_from_file(dir: &str, filename: &str) -> Vec<CurrentVersion> {
+    let Some(path) = resolution::nearest_version_file(Path::new(dir), filename) else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let data_dir = app_config::asdf_data_dir().ok();
+    parser::parse_tool_versions(&content)
+        .into_iter()
+        .filter_map(|entry| {
+            let version = entry.versions.into_iter().next()?;
+            let installed = data_dir.as_ref().is_some_and(|d| {
+                disk::version_dir(
+                    d,
+                    "installs",
+                    &entry.tool,
+                    &disk::install_dir_name(&version),
+                )
+                .is_ok_and(|p| p.is_dir())
+            });
+            Some(CurrentVersion {
+                name: entry.tool,
+                version,
+                source: path.to_string_lossy().to_string(),
+                installed,
+                release: None,
+            })
+        })
+        .collect()
+}
+
+/// Work out which tool versions asdf would use in `dir` are not installed,
+/// and which plugins are missing entirely.
+#[tauri::command]
+pub async fn plan_project_install(dir: Option<String>) -> Result<ProjectInstallPlan, AppError> {
+    let ctx = ConfigContext::load();
+    let dir = dir.unwrap_or_else(|| ctx.cwd_or_home());
+    project_install_plan(&dir, ctx.asdf_path.as_deref()).await
+}
+
+/// Install everything `plan_project_install` reports as missing, one
+/// tracked job per tool version. Missing plugins are only added when
+/// `add_plugins` is set (the frontend asks first); otherwise their
+/// versions are skipped.
+#[tauri::command]
+pub async fn install_project(
+    dir: Option<String>,
+    add_plugins: bool,
+    keep_download: bool,
+    on_event: tauri::ipc::Channel<BatchEvent>,
+) -> Result<BatchSummary, AppError> {
+    let ctx = ConfigContext::load();
+    let dir = dir.unwrap_or_else(|| ctx.cwd_or_home());
+    let plan = project_install_plan(&dir, ctx.asdf_path.as_deref()).await?;
+
+    let mut runner = BatchRunner::new(move |event| {
+        let _ = on_event.send(event);
+    });
+    let options = InstallOptions {
+        add_plugins,
+        keep_download,
+        cwd: Some(&dir),
+        asdf_path: ctx.asdf_path.as_deref(),
+    };
+    install_pairs(&mut runner, &plan.missing_plugins, &plan.missing, &options).await;
+    Ok(runner.finish())
+}
+
+/// Install every `(tool, version)` pair as its own tracked job, carrying
+/// on past failures. All pairs are validated before anything runs; pairs
+/// already installed are reported as skipped.
+#[tauri::command]
+pub async fn install_many(
+    pairs: Vec<(String, String)>,
+    add_plugins: bool,
+    keep_download: bool,
+    on_event: tauri::ipc::Channel<BatchEvent>,
+) -> Result<BatchSummary, AppError> {
+    let ctx = ConfigContext::load();
+    let asdf_path = ctx.asdf_path.as_deref();
+
+    let pairs: Vec<(String, String)> = parse_pairs(&pairs)?
+        .into_iter()
+        .map(|(tool, version)| (tool.as_str().to_string(), version.as_str().to_string()))
+        .collect();
+
+    let plugins = query::plugin_names(asdf_path).await?;
+    let installed_versions = installed_by_tool(&pairs, &plugins, asdf_path).await;
+    let InstallPlan {
+        missing_plugins,
+        missing,
+        installed,
+    } = batch::plan_installs(&pairs, &installed_versions);
+
+    let mut runner = BatchRunner::new(move |event| {
+        let _ = on_event.send(event);
+    });
+    for (tool, version) in &installed {
+        runner.skip(tool, version, "already installed".to_string());
+    }
+    let options = InstallOptions {
+        add_plugins,
+        keep_download,
+        cwd: ctx.cwd.as_deref(),
+        asdf_path,
+    };
+    install_pairs(&mut runner, &missing_plugins, &missing, &options).await;
+    Ok(runner.finish())
+}
+
+struct InstallOptions<'a> {
+    add_plugins: bool,
+    keep_download: bool,
+    cwd: Option<&'a str>,
+    asdf_path: Option<&'a str>,
+}
+
+/// Add `missing_plugins` when allowed, then install `pairs`, skipping
+/// those whose plugin is still missing.
+async fn install_pairs<F>(
+    runner: &mut BatchRunner<F>,
+    missing_plugins: &[String],
+    pairs: &[(String, String)],
+    options: &InstallOptions<'_>,
+) where
+    F: Fn(BatchEvent) + Clone + Send + 'static,
+{
+    let mut unavailable = Vec::new();
+    for name in missing_plugins {
+        if !options.add_plugins || !runner.add_plugin(name, options.asdf_path).await {
+            unavailable.push(name.as_str());
+        }
+    }
+
+    for (tool, version) in pairs {
+        if unavailable.contains(&tool.as_str()) {
+            runner.skip(tool, version, format!("plugin {tool} is not installed"));
+            continue;
+        }
+        runner
+            .install(
+                tool,
+                version,
+                options.cwd,
+                options.asdf_path,
+                options.keep_download,
+            )
+            .await;
+    }
+}
+
+/// What installing `dir`'s effective versions takes. Every tool asdf would
+/// resolve there is considered: those in version files up to `/` and in
+/// the home file, and those set through `ASDF_<TOOL>_VERSION` (Shell scope
+/// included). Each tool's versions come from the source that wins.
+async fn project_install_plan(
+    dir: &str,
+    asdf_path: Option<&str>,
+) -> Result<ProjectInstallPlan, AppError> {
+    let filename = app_config::tool_versions_filename();
+    let plugins = query::plugin_names(asdf_path).await?;
+    let pairs: Vec<(String, String)> = resolution::effective_pins(dir, &plugins)
+        .await?
+        .into_iter()
+        .flat_map(|file| file.entries)
+        .flat_map(|e| e.versions.into_iter().map(move |v| (e.tool.clone(), v)))
+        .collect();
+
+    let installed = installed_by_tool(&pairs, &plugins, asdf_path).await;
+    let InstallPlan {
+        missing_plugins,
+        missing,
+        ..
+    } = batch::plan_installs(&pairs, &installed);
+
+    let mut default_packages: Vec<DefaultPackagesFile> = Vec::new();
+    for (tool, _) in &missing {
+        if default_packages.iter().all(|f| &f.plugin != tool)
+            && let Ok(file) = default_packages::read(tool)
+            && file.exists
+        {
+            default_packages.push(file);
+        }
+    }
+
+    Ok(ProjectInstallPlan {
+        path: resolution::nearest_version_file(Path::new(dir), &filename)
+            .map(|p| p.to_string_lossy().to_string()),
+        missing_plugins,
+        missing,
+        default_packages,
+    })
+}
+
+/// Installed versions of each tool in `pairs` that has a plugin, listed
+/// once per tool.
+async fn installed_by_tool(
+    pairs: &[(String, String)],
+    plugins: &[String],
+    asdf_path: Option<&str>,
+) -> HashMap<String, Vec<String>> {
+    let mut installed = HashMap::new();
+    for (tool, _) in pairs {
+        if plugins.contains(tool) && !installed.contains_key(tool) {
+            let versions = query::installed_versions(tool, asdf_path).await;
+            installed.insert(tool.clone(), versions);
+        }
+    }
+    installed
+}
+
+/// Known version files and env vars that reference `name` `version`,
+/// checked before uninstalling it.
+#[tauri::command]
+pub async fn uninstall_impact(
+    name: String,
+    version: String,
+    roots: Vec<String>,
+) -> Result<UninstallImpact, AppError> {
+    let ctx = ConfigContext::load();
+    let plugins = query::plugin_names(ctx.asdf_path.as_deref()).await?;
+    let files = scan::known_version_files(&roots, &plugins).await?;
+    let env_name = resolution::env_var_name(&name);
+    let env: Vec<(String, String)> = user_env_var(&env_name)
+        .map(|value| (env_name, value))
+        .into_iter()
+        .collect();
+    Ok(impact::uninstall_impact(
+        &name,
+        &version,
+        &files,
+        scan::home_version_file().as_ref(),
+        &env,
+    ))
+}
+
+/// Refuses to remove the global version unless `force` is set.
 #[tauri::command]
-pub async fn uninstall(name: String, version: String) -> Result<String, AppError> {
+pub async fn uninstall(name: String, version: String, force: bool) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
+    let name = PluginName::parse(name)?;
+    let version = VersionString::parse(version)?;
+    if !force {
+        impact::ensure_not_global(
+            scan::home_version_file().as_ref(),
+            name.as_str(),
+            version.as_str(),
+        )?;
+    }
     let output = run_asdf(
-        &["uninstall", &name, &version],
+        &with_positionals(&["uninstall"], &[name.as_str(), version.as_str()]),
         None,
         ctx.asdf_path.as_deref(),
     )
@@ -65,29 +386,75 @@ pub async fn set_version(
     scope: SetScope,
 ) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let mut args: Vec<String> = vec!["set".to_string()];
+    let name = PluginName::parse(name)?;
+    let versions = versions
+        .into_iter()
+        .map(VersionString::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let flags: &[&str] = match scope {
+        SetScope::Home => &["set", "--home"],
+        SetScope::Parent => &["set", "--parent"],
+        SetScope::Local => &["set"],
+        SetScope::Shell => {
+            let project = shell_project(&ctx)?;
+            let versions: Vec<&str> = versions.iter().map(VersionString::as_str).collect();
+            shell_scope::set(&project, name.as_str(), &versions);
+            return Ok(String::new());
+        }
+    };
+    let mut positionals = vec![name.as_str()];
+    positionals.extend(versions.iter().map(VersionString::as_str));
+
+    let args = with_positionals(flags, &positionals);
+    let output = run_asdf(&args, ctx.cwd.as_deref(), ctx.asdf_path.as_deref()).await?;
+    Ok(output.stdout.trim().to_string())
+}
+
+/// The project Shell-scope overrides apply to: the working directory.
+fn shell_project(ctx: &ConfigContext) -> Result<String, AppError> {
+    ctx.cwd.clone().ok_or_else(|| {
+        AppError::ConfigError("the shell scope needs a working directory".to_string())
+    })
+}
 
-    match scope {
-        SetScope::Home => args.push("--home".to_string()),
-        SetScope::Parent => args.push("--parent".to_string()),
-        SetScope::Local => {}
+/// Shell-scope overrides, for the working directory or for every project.
+#[tauri::command]
+pub async fn shell_overrides(all: bool) -> Result<Vec<ShellOverride>, AppError> {
+    let ctx = ConfigContext::load();
+    if all {
+        return Ok(shell_scope::list(None));
     }
+    Ok(shell_scope::list(Some(&shell_project(&ctx)?)))
+}
 
-    args.push(name);
-    args.extend(versions);
+/// Drop the working directory's override for `name`, or all of them.
+#[tauri::command]
+pub async fn unset_shell_override(name: Option<String>) -> Result<bool, AppError> {
+    let ctx = ConfigContext::load();
+    let name = name.map(PluginName::parse).transpose()?;
+    Ok(shell_scope::unset(
+        &shell_project(&ctx)?,
+        name.as_ref().map(PluginName::as_str),
+    ))
+}
 
-    let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    let output = run_asdf(&args_ref, ctx.cwd.as_deref(), ctx.asdf_path.as_deref()).await?;
-    Ok(output.stdout.trim().to_string())
+/// The working directory's overrides as a snippet to paste into a terminal.
+#[tauri::command]
+pub async fn export_shell_overrides(syntax: ShellSyntax) -> Result<String, AppError> {
+    let ctx = ConfigContext::load();
+    let overrides = shell_scope::list(Some(&shell_project(&ctx)?));
+    Ok(shell_scope::render(&overrides, syntax))
 }
 
 #[tauri::command]
 pub async fn latest(name: String, filter: Option<String>) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let mut args = vec!["latest", name.as_str()];
-    if let Some(ref f) = filter {
-        args.push(f.as_str());
-    }
+    let name = PluginName::parse(name)?;
+    let filter = filter.map(VersionString::parse).transpose()?;
+    let mut positionals = vec![name.as_str()];
+    positionals.extend(filter.iter().map(VersionString::as_str));
+    let args = with_positionals(&["latest"], &positionals);
     let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
     Ok(output.stdout.trim().to_string())
 }
@@ -100,33 +467,85 @@ pub async fn latest_all() -> Result<Vec<LatestInfo>, AppError> {
 }
 
 #[tauri::command]
-pub async fn list_installed(name: String) -> Result<Vec<String>, AppError> {
+pub async fn list_installed(name: String) -> Result<Vec<VersionEntry>, AppError> {
     let ctx = ConfigContext::load();
-    let output = run_asdf(&["list", &name], None, ctx.asdf_path.as_deref()).await?;
-    Ok(parser::parse_list_installed(&output.stdout)
+    let name = PluginName::parse(name)?;
+    let args = with_positionals(&["list"], &[name.as_str()]);
+    let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
+    let versions = parser::parse_list_installed(&output.stdout)
         .into_iter()
         .map(|(v, _)| v)
-        .collect())
+        .collect();
+    Ok(releases::annotate(
+        &releases::load(),
+        name.as_str(),
+        versions,
+    ))
 }
 
 #[tauri::command]
-pub async fn list_all(name: String, filter: Option<String>) -> Result<Vec<String>, AppError> {
+pub async fn list_all(name: String, filter: Option<String>) -> Result<Vec<VersionEntry>, AppError> {
     let ctx = ConfigContext::load();
-    let mut args = vec!["list", "all", name.as_str()];
-    if let Some(ref f) = filter {
-        args.push(f.as_str());
-    }
+    let name = PluginName::parse(name)?;
+    let filter = filter.map(VersionString::parse).transpose()?;
+    let mut positionals = vec![name.as_str()];
+    positionals.extend(filter.iter().map(VersionString::as_str));
+    let args = with_positionals(&["list", "all"], &positionals);
     let output = run_asdf(&args, None, ctx.asdf_path.as_deref()).await?;
-    Ok(parser::parse_list_all(&output.stdout))
+    let versions = parser::parse_list_all(&output.stdout);
+    if filter.is_none() {
+        cache::store_list_all(name.as_str(), &versions);
+    }
+    Ok(releases::annotate(
+        &releases::load(),
+        name.as_str(),
+        versions,
+    ))
+}
+
+/// Search, filter and page through a plugin's available versions. Works
+/// over the cached `list_all` result, fetching it once if needed.
+#[tauri::command]
+pub async fn query_versions(name: String, query: VersionQuery) -> Result<VersionPage, AppError> {
+    let ctx = ConfigContext::load();
+    let name = PluginName::parse(name)?;
+    let available = query::available_versions(name.as_str(), ctx.asdf_path.as_deref()).await?;
+    let (total, page, groups) = search::search_versions(&available, &query)?;
+    Ok(VersionPage {
+        total,
+        offset: query.offset,
+        versions: releases::annotate(&releases::load(), name.as_str(), page),
+        groups,
+    })
 }
 
 #[tauri::command]
 pub async fn where_installed(name: String, version: Option<String>) -> Result<String, AppError> {
     let ctx = ConfigContext::load();
-    let mut args = vec!["where", name.as_str()];
-    if let Some(ref v) = version {
-        args.push(v.as_str());
-    }
+    let name = PluginName::parse(name)?;
+    let version = version.map(VersionString::parse).transpose()?;
+    let mut positionals = vec![name.as_str()];
+    positionals.extend(version.iter().map(VersionString::as_str));
+    let args = with_positionals(&["where"], &positionals);
     let output = run_asdf(&args, ctx.cwd.as_deref(), ctx.asdf_path.as_deref()).await?;
     Ok(output.stdout.trim().to_string())
 }
+
+#[tauri::command]
+pub async fn explain_resolution(
+    dir: Option<String>,
+    tool: String,
+) -> Result<ResolutionExplanation, AppError> {
+    let ctx = ConfigContext::load();
+    // The name picks a plugin script to run, so it must not leave the
+    // plugins directory.
+    let name = PluginName::parse(tool.as_str())?;
+    let dir = dir.unwrap_or_else(|| ctx.cwd_or_home());
+
+    let settings = resolution::resolution_settings(&dir, &name).await?;
+    Ok(resolution::explain_resolution(
+        &PathBuf::from(dir),
+        &tool,
+        &settings,
+    ))
+}
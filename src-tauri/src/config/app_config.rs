@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::env::user_env_var;
 use crate::error::AppError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +16,12 @@ pub struct AppConfig {
     pub keep_downloads: bool,
     pub notifications: bool,
     pub recent_projects: Vec<RecentProject>,
+    /// Directories searched for version files by cross-project views.
+    #[serde(default)]
+    pub scan_roots: Vec<String>,
+    /// Git ref (tag, branch or commit) each plugin should be checked out at.
+    #[serde(default)]
+    pub plugin_pins: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +41,8 @@ impl Default for AppConfig {
             keep_downloads: false,
             notifications: true,
             recent_projects: Vec::new(),
+            scan_roots: Vec::new(),
+            plugin_pins: BTreeMap::new(),
         }
     }
 }
@@ -46,6 +57,12 @@ fn config_path() -> Result<PathBuf, AppError> {
     Ok(config_dir()?.join("config.json"))
 }
 
+/// User-maintained release metadata that extends or corrects the bundled
+/// dataset.
+pub fn release_metadata_path() -> Result<PathBuf, AppError> {
+    Ok(config_dir()?.join("release-metadata.json"))
+}
+
 pub fn read_config() -> Result<AppConfig, AppError> {
     let path = config_path()?;
     if !path.exists() {
@@ -67,14 +84,29 @@ pub fn write_config(config: &AppConfig) -> Result<(), AppError> {
     Ok(())
 }
 
-/// Read .asdfrc as key-value pairs.
-pub fn read_asdfrc() -> Result<Vec<(String, String)>, AppError> {
+/// Settings saved by the frontend, with the fields only the backend edits
+/// taken from `current`. The frontend writes back its cached copy, which
+/// would otherwise drop plugin pins set since it was loaded.
+pub fn merge_frontend_config(incoming: AppConfig, current: &AppConfig) -> AppConfig {
+    AppConfig {
+        plugin_pins: current.plugin_pins.clone(),
+        ..incoming
+    }
+}
+
+/// Resolve the .asdfrc location, honoring `$ASDF_CONFIG_FILE`.
+pub fn asdfrc_path() -> Result<PathBuf, AppError> {
     let home = dirs::home_dir()
         .ok_or_else(|| AppError::ConfigError("cannot determine home directory".to_string()))?;
 
-    let asdfrc_path = std::env::var("ASDF_CONFIG_FILE")
+    Ok(std::env::var("ASDF_CONFIG_FILE")
         .map(PathBuf::from)
-        .unwrap_or_else(|_| home.join(".asdfrc"));
+        .unwrap_or_else(|_| home.join(".asdfrc")))
+}
+
+/// Read .asdfrc as key-value pairs.
+pub fn read_asdfrc() -> Result<Vec<(String, String)>, AppError> {
+    let asdfrc_path = asdfrc_path()?;
 
     if !asdfrc_path.exists() {
         return Ok(Vec::new());
@@ -95,3 +127,115 @@ pub fn read_asdfrc() -> Result<Vec<(String, String)>, AppError> {
 
     Ok(pairs)
 }
+
+/// Name asdf uses for version files when nothing overrides it.
+pub const DEFAULT_TOOL_VERSIONS_FILENAME: &str = ".tool-versions";
+
+/// The resolved version file name, shared by every caller until
+/// `invalidate_tool_versions_filename` clears it.
+static TOOL_VERSIONS_FILENAME: Mutex<Option<String>> = Mutex::new(None);
+
+/// The version file name, resolved once: see `resolve_tool_versions_filename`.
+pub fn tool_versions_filename() -> String {
+    let Ok(mut cached) = TOOL_VERSIONS_FILENAME.lock() else {
+        return resolve_tool_versions_filename();
+    };
+    cached
+        .get_or_insert_with(resolve_tool_versions_filename)
+        .clone()
+}
+
+/// Forget the cached name; the watcher calls this when .asdfrc changes.
+pub fn invalidate_tool_versions_filename() {
+    if let Ok(mut cached) = TOOL_VERSIONS_FILENAME.lock() {
+        *cached = None;
+    }
+}
+
+fn resolve_tool_versions_filename() -> String {
+    pick_tool_versions_filename(
+        user_env_var("ASDF_DEFAULT_TOOL_VERSIONS_FILENAME"),
+        &read_asdfrc().unwrap_or_default(),
+    )
+}
+
+/// `$ASDF_DEFAULT_TOOL_VERSIONS_FILENAME` first, then a
+/// `default_tool_versions_filename` key in .asdfrc, then the default.
+/// Values that are not a bare file name are skipped.
+fn pick_tool_versions_filename(env: Option<String>, asdfrc: &[(String, String)]) -> String {
+    let from_asdfrc = asdfrc
+        .iter()
+        .find(|(k, _)| k == "default_tool_versions_filename")
+        .map(|(_, v)| v.clone());
+    env.into_iter()
+        .chain(from_asdfrc)
+        .find(|name| is_bare_file_name(name))
+        .unwrap_or_else(|| DEFAULT_TOOL_VERSIONS_FILENAME.to_string())
+}
+
+fn is_bare_file_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+/// Resolve `$ASDF_DATA_DIR`, defaulting to `~/.asdf` like asdf itself.
+pub fn asdf_data_dir() -> Result<PathBuf, AppError> {
+    if let Some(dir) = user_env_var("ASDF_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let home = dirs::home_dir()
+        .ok_or_else(|| AppError::ConfigError("cannot determine home directory".to_string()))?;
+    Ok(home.join(".asdf"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontend_write_keeps_plugin_pins() {
+        let cached = AppConfig::default();
+        let mut on_disk = cached.clone();
+        on_disk
+            .plugin_pins
+            .insert("nodejs".to_string(), "v2.1.0".to_string());
+
+        let saved = merge_frontend_config(
+            AppConfig {
+                theme: "dark".to_string(),
+                ..cached
+            },
+            &on_disk,
+        );
+        let json = serde_json::to_string(&saved).unwrap();
+        let read: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.theme, "dark");
+        assert_eq!(read.plugin_pins["nodejs"], "v2.1.0");
+    }
+
+    #[test]
+    fn test_pick_tool_versions_filename_precedence() {
+        let asdfrc = vec![(
+            "default_tool_versions_filename".to_string(),
+            ".asdf-versions".to_string(),
+        )];
+        assert_eq!(
+            pick_tool_versions_filename(Some(".versions".to_string()), &asdfrc),
+            ".versions"
+        );
+        assert_eq!(pick_tool_versions_filename(None, &asdfrc), ".asdf-versions");
+        assert_eq!(
+            pick_tool_versions_filename(Some("../x".to_string()), &asdfrc),
+            ".asdf-versions"
+        );
+        assert_eq!(pick_tool_versions_filename(None, &[]), ".tool-versions");
+    }
+
+    #[test]
+    fn test_is_bare_file_name() {
+        assert!(is_bare_file_name(".tool-versions"));
+        assert!(is_bare_file_name("versions.txt"));
+        for bad in ["", ".", "..", "dir/.tool-versions", "/etc/passwd", "a\\b"] {
+            assert!(!is_bare_file_name(bad), "accepted {bad:?}");
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Cache the user's login-shell environment so we only resolve it once.
+static LOGIN_ENV: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Get the user's environment by invoking their login shell.
+/// Tauri apps launched from Finder/Dock inherit a minimal environment
+/// (PATH is /usr/bin:/bin:/usr/sbin:/sbin), so we need the real one.
+fn login_env() -> &'static HashMap<String, String> {
+    LOGIN_ENV.get_or_init(|| {
+        // Try the user's SHELL, fallback to /bin/zsh (macOS default), then /bin/bash
+        let shells = [
+            std::env::var("SHELL").unwrap_or_default(),
+            "/bin/zsh".to_string(),
+            "/bin/bash".to_string(),
+        ];
+
+        for shell in &shells {
+            if shell.is_empty() {
+                continue;
+            }
+            if let Ok(output) = std::process::Command::new(shell)
+                .args(["-lc", "env"])
+                .output()
+                && output.status.success()
+            {
+                let env: HashMap<String, String> = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter_map(|line| line.split_once('='))
+                    .filter(|(key, _)| {
+                        !key.is_empty()
+                            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    })
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect();
+                if env.get("PATH").is_some_and(|p| !p.is_empty()) {
+                    return env;
+                }
+            }
+        }
+
+        // Last resort: the current (minimal) environment
+        std::env::vars().collect()
+    })
+}
+
+/// Get the user's full PATH from the login environment.
+pub fn get_user_path() -> String {
+    login_env()
+        .get("PATH")
+        .cloned()
+        .unwrap_or_else(|| std::env::var("PATH").unwrap_or_default())
+}
+
+/// Look up a variable the user has set, preferring the app's own
+/// environment (inherited by asdf child processes) over the login shell's.
+pub fn user_env_var(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .or_else(|| login_env().get(key).cloned())
+        .filter(|v| !v.is_empty())
+}
@@ -17,6 +17,12 @@ pub enum AppError {
     #[error("config error: {0}")]
     ConfigError(String),
 
+    #[error("path not allowed: {0}")]
+    PathNotAllowed(String),
+
+    #[error("version in use: {0}")]
+    InUse(String),
+
     #[error("{0}")]
     Io(#[from] std::io::Error),
 }
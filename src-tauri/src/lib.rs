@@ -2,13 +2,21 @@ mod asdf;
 mod commands;
 mod config;
 mod error;
+mod path_guard;
 mod types;
+mod watcher;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_log::Builder::new().build())
+        .manage(watcher::WatcherState::default())
+        .setup(|app| {
+            watcher::refresh(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Info
             commands::info::asdf_version,
@@ -20,16 +28,32 @@ pub fn run() {
             commands::plugin::plugin_add,
             commands::plugin::plugin_remove,
             commands::plugin::plugin_update,
+            commands::plugin::plugin_details,
+            commands::plugin::set_plugin_pin,
+            commands::plugin::plugin_drift,
+            commands::plugin::default_packages_files,
+            commands::plugin::read_default_packages,
+            commands::plugin::write_default_packages,
             // Versions
             commands::version::current,
             commands::version::install,
+            commands::version::plan_project_install,
+            commands::version::install_project,
+            commands::version::install_many,
+            commands::version::uninstall_impact,
             commands::version::uninstall,
             commands::version::set_version,
+            commands::version::shell_overrides,
+            commands::version::unset_shell_override,
+            commands::version::export_shell_overrides,
             commands::version::latest,
             commands::version::latest_all,
             commands::version::list_installed,
             commands::version::list_all,
+            commands::version::query_versions,
             commands::version::where_installed,
+            commands::version::explain_resolution,
+            commands::version::version_matrix,
             // Shims
             commands::shim::which_command,
             commands::shim::shim_versions,
@@ -37,12 +61,34 @@ pub fn run() {
             // .tool-versions
             commands::tool_versions::read_tool_versions,
             commands::tool_versions::write_tool_versions,
+            commands::tool_versions::lint_tool_versions,
+            commands::tool_versions::scan_tool_versions,
+            commands::tool_versions::diff_tool_versions,
+            commands::tool_versions::merge_tool_versions,
+            commands::tool_versions::plan_upgrades,
+            commands::tool_versions::apply_upgrade_plan,
             commands::tool_versions::get_tool_versions_path,
+            // Ecosystem version files
+            commands::ecosystem::export_tool_versions,
+            commands::ecosystem::suggest_tool_versions,
+            // End-of-life
+            commands::eol::eol_report,
+            commands::eol::export_eol_report,
+            // Disk
+            commands::disk::disk_usage,
+            commands::disk::gc_plan,
+            commands::disk::gc_apply,
+            commands::disk::list_downloads,
+            commands::disk::delete_downloads,
+            commands::disk::prune_downloads,
+            commands::disk::reinstall_from_download,
             // Settings
             commands::settings::read_config,
             commands::settings::write_config,
             commands::settings::set_working_directory,
             commands::settings::read_asdfrc,
+            commands::settings::watcher_error,
+            commands::settings::release_metadata_error,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
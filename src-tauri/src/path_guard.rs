@@ -0,0 +1,140 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::config::app_config;
+use crate::error::AppError;
+
+/// Check a frontend-supplied version file path before reading or writing
+/// it. The path must be absolute, name a version file, and resolve
+/// (following symlinks) to a version file inside the home directory, the
+/// working directory or a recent project. Returns the resolved path; a
+/// `PathNotAllowed` error names the path and why it was refused, and the
+/// refusal is logged.
+pub fn check_version_file(path: &str) -> Result<PathBuf, AppError> {
+    let names = [
+        app_config::tool_versions_filename(),
+        app_config::DEFAULT_TOOL_VERSIONS_FILENAME.to_string(),
+    ];
+    check(Path::new(path), &names, &allowed_roots())
+}
+
+/// The same checks for another tool's file written next to a version file,
+/// such as an exported `.nvmrc`: it must be called `name` and resolve
+/// inside the same roots.
+pub fn check_named_file(path: &Path, name: &str) -> Result<PathBuf, AppError> {
+    check(path, &[name.to_string()], &allowed_roots())
+}
+
+/// Home, the configured working directory and recent projects, resolved so
+/// they compare against resolved paths.
+fn allowed_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = dirs::home_dir().into_iter().collect();
+    if let Ok(config) = app_config::read_config() {
+        roots.extend(config.working_directory.map(PathBuf::from));
+        roots.extend(config.recent_projects.into_iter().map(|p| p.path.into()));
+    }
+    roots
+        .iter()
+        .filter_map(|r| std::fs::canonicalize(r).ok())
+        .collect()
+}
+
+fn check(path: &Path, names: &[String], roots: &[PathBuf]) -> Result<PathBuf, AppError> {
+    let reject = |reason: &str| {
+        log::warn!("refused version file path {}: {reason}", path.display());
+        AppError::PathNotAllowed(format!("{}: {reason}", path.display()))
+    };
+    let is_version_file = |p: &Path| {
+        p.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| names.iter().any(|name| name == n))
+    };
+
+    if !path.is_absolute() {
+        return Err(reject("not an absolute path"));
+    }
+    if !is_version_file(path) {
+        return Err(reject("not a version file"));
+    }
+
+    // An existing entry (including a dangling symlink) must resolve in
+    // full; a new file is resolved through its parent directory.
+    let resolved = match std::fs::symlink_metadata(path) {
+        Ok(_) => std::fs::canonicalize(path).map_err(|_| reject("dangling symlink"))?,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let parent = path.parent().ok_or_else(|| reject("no parent directory"))?;
+            let parent =
+                std::fs::canonicalize(parent).map_err(|_| reject("parent directory not found"))?;
+            parent.join(path.file_name().ok_or_else(|| reject("no file name"))?)
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if !is_version_file(&resolved) {
+        return Err(reject("symlink to a file that is not a version file"));
+    }
+    if !roots.iter().any(|root| resolved.starts_with(root)) {
+        return Err(reject("outside the home directory and known projects"));
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asdf-gui-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("project")).unwrap();
+        std::fs::canonicalize(dir).unwrap()
+    }
+
+    #[test]
+    fn test_check_allows_version_files_in_roots() {
+        let dir = sandbox("allow");
+        let names = [".tool-versions".to_string()];
+        let roots = [dir.join("project")];
+        let path = dir.join("project/.tool-versions");
+
+        assert_eq!(check(&path, &names, &roots).unwrap(), path);
+        std::fs::write(&path, "nodejs 20.11.0\n").unwrap();
+        assert_eq!(check(&path, &names, &roots).unwrap(), path);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_rejects_escapes() {
+        let dir = sandbox("reject");
+        let names = [".tool-versions".to_string()];
+        let roots = [dir.join("project")];
+        let rejected =
+            |p: PathBuf| matches!(check(&p, &names, &roots), Err(AppError::PathNotAllowed(_)));
+
+        assert!(rejected(PathBuf::from("project/.tool-versions")));
+        assert!(rejected(dir.join("project/.bashrc")));
+        assert!(rejected(dir.join(".tool-versions")));
+        assert!(rejected(dir.join("project/../.tool-versions")));
+
+        std::fs::write(dir.join(".bashrc"), "").unwrap();
+        let link = dir.join("project/.tool-versions");
+        std::os::unix::fs::symlink(dir.join(".bashrc"), &link).unwrap();
+        assert!(rejected(link.clone()));
+
+        std::fs::remove_file(&link).unwrap();
+        std::os::unix::fs::symlink(dir.join("missing"), &link).unwrap();
+        assert!(rejected(link));
+
+        // An export target with the right name that points outside the roots.
+        std::fs::write(dir.join(".nvmrc"), "20\n").unwrap();
+        let nvmrc = dir.join("project/.nvmrc");
+        std::os::unix::fs::symlink(dir.join(".nvmrc"), &nvmrc).unwrap();
+        let names = [".nvmrc".to_string()];
+        assert!(matches!(
+            check(&nvmrc, &names, &roots),
+            Err(AppError::PathNotAllowed(_))
+        ));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -19,6 +19,7 @@ pub struct CurrentVersion {
     pub version: String,
     pub source: String,
     pub installed: bool,
+    pub release: Option<ReleaseInfo>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +63,8 @@ pub enum SetScope {
     Local,
     Home,
     Parent,
+    /// `ASDF_<TOOL>_VERSION` for the working directory, held by the app.
+    Shell,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,3 +73,483 @@ pub enum InstallEvent {
     Stderr(String),
     Finished { success: bool },
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintDiagnostic {
+    pub line: usize,
+    pub severity: LintSeverity,
+    pub tool: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolVersionsFile {
+    pub path: String,
+    pub entries: Vec<ToolVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolPin {
+    pub tool: String,
+    pub version: String,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolVersionsScan {
+    pub files: Vec<ToolVersionsFile>,
+    pub pins: Vec<ToolPin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResolutionSourceKind {
+    EnvVar,
+    ToolVersions,
+    LegacyFile,
+    Home,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionCandidate {
+    pub kind: ResolutionSourceKind,
+    pub location: String,
+    pub exists: bool,
+    pub matched: bool,
+    pub versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionExplanation {
+    pub tool: String,
+    pub dir: String,
+    pub candidates: Vec<ResolutionCandidate>,
+    pub winner: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolVersionChange {
+    pub tool: String,
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolVersionsDiff {
+    pub added: Vec<ToolVersion>,
+    pub removed: Vec<ToolVersion>,
+    pub changed: Vec<ToolVersionChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
+pub enum MergeStrategy {
+    PreferLeft,
+    PreferRight,
+    PreferNewer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatchKind {
+    ToolVersions,
+    HomeToolVersions,
+    Asdfrc,
+    Installs,
+    Plugins,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub kind: WatchKind,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportTarget {
+    Nvmrc,
+    PythonVersion,
+    RustToolchain,
+    MiseToml,
+    GoMod,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportStatus {
+    Created,
+    Updated,
+    Unchanged,
+    Conflict,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub target: ExportTarget,
+    pub path: String,
+    pub status: ExportStatus,
+    pub conflicts: Vec<String>,
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolSuggestion {
+    pub tool: String,
+    pub source: String,
+    pub constraint: String,
+    pub resolved: Option<String>,
+    pub selected: bool,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolVersionsSuggestion {
+    pub suggestions: Vec<ToolSuggestion>,
+    pub entries: Vec<ToolVersion>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobUpdate {
+    pub tool: String,
+    pub version: String,
+    pub status: JobStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub jobs: Vec<JobUpdate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BatchEvent {
+    Plugin {
+        name: String,
+        success: bool,
+        message: Option<String>,
+    },
+    Job(JobUpdate),
+    Output {
+        tool: String,
+        version: String,
+        event: InstallEvent,
+    },
+    Summary(BatchSummary),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectInstallPlan {
+    /// Nearest version file, if any; versions may also come from parent
+    /// files, the home file or env overrides.
+    pub path: Option<String>,
+    pub missing_plugins: Vec<String>,
+    pub missing: Vec<(String, String)>,
+    /// Default-packages files that will be applied to the new installs.
+    pub default_packages: Vec<DefaultPackagesFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum UpgradePolicy {
+    Patch,
+    Minor,
+    Major,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeCandidate {
+    pub tool: String,
+    pub current: String,
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradePlan {
+    pub path: String,
+    pub policy: UpgradePolicy,
+    pub upgrades: Vec<UpgradeCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionUsage {
+    pub version: String,
+    /// False when only a download is left behind.
+    pub installed: bool,
+    pub install_bytes: u64,
+    pub download_bytes: u64,
+    /// Newest modification time in the install tree (unix seconds).
+    pub modified: Option<u64>,
+    /// Newest access time of the install's `bin` entries, when the
+    /// filesystem records it (unix seconds).
+    pub last_used: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginUsage {
+    pub name: String,
+    pub plugin_bytes: u64,
+    pub install_bytes: u64,
+    pub download_bytes: u64,
+    pub versions: Vec<VersionUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsage {
+    pub data_dir: String,
+    pub installs_bytes: u64,
+    pub downloads_bytes: u64,
+    pub plugins_bytes: u64,
+    pub total_bytes: u64,
+    pub plugins: Vec<PluginUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcCandidate {
+    pub tool: String,
+    /// As asdf takes it, so `ref:<rev>` rather than the `ref-<rev>` dir.
+    pub version: String,
+    /// Install plus leftover download size.
+    pub bytes: u64,
+    pub last_used: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcPlan {
+    /// Version files whose pins were kept.
+    pub files: Vec<String>,
+    pub candidates: Vec<GcCandidate>,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UninstallImpact {
+    pub tool: String,
+    pub version: String,
+    /// Known version files that pin this version.
+    pub files: Vec<String>,
+    /// `NAME=value` environment variables that select this version.
+    pub env_vars: Vec<String>,
+    /// Pinned first in the home version file.
+    pub is_global: bool,
+}
+
+/// Release line metadata, from the bundled dataset or the user's
+/// override file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    pub cycle: String,
+    #[serde(default)]
+    pub lts: bool,
+    /// ISO dates (`YYYY-MM-DD`).
+    #[serde(default)]
+    pub release_date: Option<String>,
+    #[serde(default)]
+    pub eol: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionEntry {
+    pub version: String,
+    pub release: Option<ReleaseInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EolStatus {
+    Expired,
+    ExpiringSoon,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EolEntry {
+    pub tool: String,
+    pub version: String,
+    pub cycle: String,
+    pub eol: String,
+    /// Negative once past end-of-life.
+    pub days_left: i64,
+    pub status: EolStatus,
+    /// Version files pinning this version.
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EolReport {
+    /// ISO date the report was computed for.
+    pub date: String,
+    pub within_days: u32,
+    pub files: Vec<String>,
+    pub entries: Vec<EolEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReportFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum SearchMode {
+    #[default]
+    Substring,
+    Regex,
+    /// Characters of the search appear in order, not necessarily adjacent.
+    Fuzzy,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VersionQuery {
+    pub search: Option<String>,
+    pub mode: SearchMode,
+    pub include_prerelease: bool,
+    pub group_by_major: bool,
+    pub order: SortOrder,
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionGroup {
+    pub major: String,
+    pub count: usize,
+    pub newest: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionPage {
+    /// Matches before pagination.
+    pub total: usize,
+    pub offset: usize,
+    pub versions: Vec<VersionEntry>,
+    /// Per major line over all matches; empty unless grouping was asked for.
+    pub groups: Vec<VersionGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadEntry {
+    pub tool: String,
+    pub version: String,
+    pub bytes: u64,
+    pub modified: Option<u64>,
+    /// Whether the matching version is still installed.
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixCell {
+    pub version: String,
+    /// Where the version was resolved from.
+    pub source: String,
+    pub installed: bool,
+}
+
+/// Effective tool versions per project. `cells[t][p]` is tool `t` in
+/// project `p`, `None` when the project resolves no version for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionMatrix {
+    pub tools: Vec<String>,
+    pub projects: Vec<String>,
+    pub cells: Vec<Vec<Option<MatrixCell>>>,
+    /// Cells whose version is not installed locally.
+    pub missing: usize,
+    /// Why each scan root that could not be walked was left out.
+    pub skipped_roots: Vec<String>,
+}
+
+/// A plugin's default-packages file, installed into every new version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefaultPackagesFile {
+    pub plugin: String,
+    pub path: String,
+    pub exists: bool,
+    /// Package lines with comments stripped, in file order.
+    pub packages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShellOverride {
+    pub project: String,
+    pub tool: String,
+    pub env_var: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShellSyntax {
+    Posix,
+    Fish,
+    PowerShell,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHead {
+    pub commit: String,
+    /// `None` when HEAD is detached.
+    pub branch: Option<String>,
+    pub tags: Vec<String>,
+    /// Committer date, ISO 8601.
+    pub date: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginDetails {
+    pub name: String,
+    pub path: String,
+    /// `None` when the plugin directory is not a git checkout.
+    pub head: Option<GitHead>,
+    pub remote_url: Option<String>,
+    pub dirty: bool,
+    /// Scripts present in `bin/`.
+    pub callbacks: Vec<String>,
+    /// Required callbacks the plugin lacks.
+    pub missing_callbacks: Vec<String>,
+    pub readme: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DriftStatus {
+    Unpinned,
+    /// HEAD is the pinned commit.
+    Matches,
+    /// HEAD is some other commit.
+    Drifted,
+    /// The pin does not resolve in the local checkout, or there is no
+    /// checkout to resolve it in.
+    Unresolved,
+    /// Pinned but not installed.
+    NotInstalled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginDrift {
+    pub name: String,
+    pub pin: Option<String>,
+    /// Commit the pin resolves to locally.
+    pub pin_commit: Option<String>,
+    pub head: Option<GitHead>,
+    pub status: DriftStatus,
+}
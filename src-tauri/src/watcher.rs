@@ -0,0 +1,315 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::app_config;
+use crate::config::context::ConfigContext;
+use crate::error::AppError;
+use crate::types::{WatchEvent, WatchKind};
+
+/// Tauri event emitted with a `WatchEvent` payload whenever watched asdf
+/// state changes on disk.
+pub const CHANGE_EVENT: &str = "asdf-changed";
+
+/// Tauri event emitted with a message when watching fails; live updates
+/// stop until the next refresh.
+pub const ERROR_EVENT: &str = "asdf-watch-error";
+
+/// Editors and `asdf install` touch files in bursts; coalesce them.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Holds the active debouncer, and the last failure so a frontend that
+/// missed the error event can still ask for it. Replacing the debouncer
+/// drops the old watches.
+#[derive(Default)]
+pub struct WatcherState {
+    active: Mutex<Option<Debouncer<RecommendedWatcher>>>,
+    error: Mutex<Option<String>>,
+}
+
+impl WatcherState {
+    pub fn error(&self) -> Option<String> {
+        self.error.lock().ok().and_then(|e| e.clone())
+    }
+}
+
+/// Paths whose changes are reported, resolved from the current config.
+#[derive(Clone)]
+struct WatchTargets {
+    cwd: Option<PathBuf>,
+    home: Option<PathBuf>,
+    asdfrc: PathBuf,
+    installs: PathBuf,
+    plugins: PathBuf,
+    filename: String,
+}
+
+impl WatchTargets {
+    fn load() -> Result<Self, AppError> {
+        let ctx = ConfigContext::load();
+        let data_dir = app_config::asdf_data_dir()?;
+        Ok(Self {
+            cwd: ctx.cwd.map(PathBuf::from),
+            home: dirs::home_dir(),
+            asdfrc: app_config::asdfrc_path()?,
+            installs: data_dir.join("installs"),
+            plugins: data_dir.join("plugins"),
+            filename: app_config::tool_versions_filename(),
+        }
+        .canonical())
+    }
+
+    /// The same targets with symlinks resolved, as event paths arrive.
+    fn canonical(self) -> Self {
+        Self {
+            cwd: self.cwd.as_deref().map(canonical),
+            home: self.home.as_deref().map(canonical),
+            asdfrc: canonical(&self.asdfrc),
+            installs: canonical(&self.installs),
+            plugins: canonical(&self.plugins),
+            filename: self.filename,
+        }
+    }
+
+    /// `path` is compared after resolving symlinks, so a project reached
+    /// through a link (macOS `/var` is `/private/var`) still matches.
+    fn classify(&self, path: &Path) -> Option<WatchKind> {
+        let path = canonical(path);
+        let path = path.as_path();
+        if path.starts_with(&self.installs) {
+            return Some(WatchKind::Installs);
+        }
+        if path.starts_with(&self.plugins) {
+            return Some(WatchKind::Plugins);
+        }
+        if path == self.asdfrc {
+            return Some(WatchKind::Asdfrc);
+        }
+        if path.file_name()? != self.filename.as_str() {
+            return None;
+        }
+        let parent = path.parent()?;
+        if self.cwd.as_deref() == Some(parent) {
+            Some(WatchKind::ToolVersions)
+        } else if self.home.as_deref() == Some(parent) {
+            Some(WatchKind::HomeToolVersions)
+        } else {
+            None
+        }
+    }
+
+    /// A plugin's first install creates `installs/<plugin>`, which needs its
+    /// own watch for later versions to be seen.
+    fn is_new_plugin_installs(&self, path: &Path) -> bool {
+        path.parent() == Some(self.installs.as_path())
+    }
+
+    /// Directories to watch. Installs are watched one level deep so new
+    /// versions show up without recursing into every installed tree.
+    fn watch_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        dirs.extend(self.cwd.clone());
+        dirs.extend(self.home.clone());
+        dirs.extend(self.asdfrc.parent().map(Path::to_path_buf));
+        dirs.push(self.plugins.clone());
+        dirs.push(self.installs.clone());
+        if let Ok(entries) = std::fs::read_dir(&self.installs) {
+            dirs.extend(entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()));
+        }
+        dirs.sort();
+        dirs.dedup();
+        dirs
+    }
+}
+
+/// `path` with symlinks resolved. Removed files are resolved through their
+/// parent directory; paths that cannot be resolved are kept as they are.
+fn canonical(path: &Path) -> PathBuf {
+    if let Ok(resolved) = std::fs::canonicalize(path) {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => std::fs::canonicalize(parent)
+            .map(|p| p.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// (Re)start watching for the current working directory. Failures are
+/// reported with `ERROR_EVENT` rather than returned: the app works without
+/// live updates.
+pub fn refresh(app: &AppHandle) {
+    let error = try_refresh(app)
+        .err()
+        .map(|e| format!("file watcher disabled: {e}"));
+    if let Ok(mut last) = app.state::<WatcherState>().error.lock() {
+        last.clone_from(&error);
+    }
+    if let Some(message) = error {
+        report(app, message);
+    }
+}
+
+fn report(app: &AppHandle, message: String) {
+    let _ = app.emit(ERROR_EVENT, message);
+}
+
+/// Add watches to the active debouncer without rebuilding it.
+fn watch_more(app: &AppHandle, dirs: &[PathBuf]) {
+    let state = app.state::<WatcherState>();
+    let Ok(mut active) = state.active.lock() else {
+        return;
+    };
+    let Some(debouncer) = active.as_mut() else {
+        return;
+    };
+    for dir in dirs {
+        if let Err(e) = debouncer.watcher().watch(dir, RecursiveMode::NonRecursive) {
+            report(app, format!("cannot watch {}: {e}", dir.display()));
+        }
+    }
+}
+
+/// Classify and emit one debounced batch. Runs on the watcher's own
+/// thread, never inside the debouncer, so it may replace the debouncer.
+fn handle_events(handle: &AppHandle, classifier: &WatchTargets, res: DebounceEventResult) {
+    let events = match res {
+        Ok(events) => events,
+        Err(e) => {
+            report(handle, format!("file watcher error: {e}"));
+            return;
+        }
+    };
+    let mut batches: Vec<WatchEvent> = Vec::new();
+    let mut new_dirs: Vec<PathBuf> = Vec::new();
+    for event in events {
+        let Some(kind) = classifier.classify(&event.path) else {
+            continue;
+        };
+        if classifier.is_new_plugin_installs(&canonical(&event.path)) && event.path.is_dir() {
+            new_dirs.push(event.path.clone());
+        }
+        let path = event.path.to_string_lossy().to_string();
+        match batches.iter_mut().find(|b| b.kind == kind) {
+            Some(batch) => batch.paths.push(path),
+            None => batches.push(WatchEvent {
+                kind,
+                paths: vec![path],
+            }),
+        }
+    }
+    watch_more(handle, &new_dirs);
+    if batches.iter().any(|b| b.kind == WatchKind::Asdfrc) {
+        app_config::invalidate_tool_versions_filename();
+        // A new version file name changes which files are watched.
+        if app_config::tool_versions_filename() != classifier.filename {
+            refresh(handle);
+        }
+    }
+    for batch in batches {
+        let _ = handle.emit(CHANGE_EVENT, batch);
+    }
+}
+
+fn try_refresh(app: &AppHandle) -> Result<(), AppError> {
+    let targets = WatchTargets::load()?;
+    let classifier = targets.clone();
+    let handle = app.clone();
+
+    // The debouncer only forwards batches. The worker ends once the
+    // debouncer, and with it the sender, is dropped by the next refresh.
+    let (tx, rx) = std::sync::mpsc::channel::<DebounceEventResult>();
+    let mut debouncer =
+        new_debouncer(DEBOUNCE, tx).map_err(|e| AppError::ProcessError(e.to_string()))?;
+    std::thread::spawn(move || {
+        for res in rx {
+            handle_events(&handle, &classifier, res);
+        }
+    });
+
+    for dir in targets.watch_dirs() {
+        // Missing directories (no plugins yet, no cwd) are simply skipped.
+        let _ = debouncer.watcher().watch(&dir, RecursiveMode::NonRecursive);
+    }
+
+    let state = app.state::<WatcherState>();
+    let mut active = state
+        .active
+        .lock()
+        .map_err(|e| AppError::ProcessError(e.to_string()))?;
+    *active = Some(debouncer);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let targets = WatchTargets {
+            cwd: Some(PathBuf::from("/work/app")),
+            home: Some(PathBuf::from("/home/user")),
+            asdfrc: PathBuf::from("/home/user/.asdfrc"),
+            installs: PathBuf::from("/home/user/.asdf/installs"),
+            plugins: PathBuf::from("/home/user/.asdf/plugins"),
+            filename: ".tool-versions".to_string(),
+        };
+        assert_eq!(
+            targets.classify(Path::new("/work/app/.tool-versions")),
+            Some(WatchKind::ToolVersions)
+        );
+        assert_eq!(
+            targets.classify(Path::new("/home/user/.tool-versions")),
+            Some(WatchKind::HomeToolVersions)
+        );
+        assert_eq!(
+            targets.classify(Path::new("/home/user/.asdfrc")),
+            Some(WatchKind::Asdfrc)
+        );
+        assert_eq!(
+            targets.classify(Path::new("/home/user/.asdf/installs/nodejs/20.11.0")),
+            Some(WatchKind::Installs)
+        );
+        assert_eq!(targets.classify(Path::new("/home/user/.zsh_history")), None);
+        assert!(targets.is_new_plugin_installs(Path::new("/home/user/.asdf/installs/deno")));
+        assert!(!targets.is_new_plugin_installs(Path::new("/home/user/.asdf/installs/deno/2.1.4")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_classify_through_symlinked_project() {
+        let root = std::env::temp_dir().join(format!("asdf-gui-watch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let real = root.join("real");
+        let link = root.join("link");
+        std::fs::create_dir_all(&real).unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+        std::fs::write(real.join(".tool-versions"), "nodejs 20.11.0\n").unwrap();
+
+        let targets = WatchTargets {
+            cwd: Some(link.clone()),
+            home: None,
+            asdfrc: root.join(".asdfrc"),
+            installs: root.join("installs"),
+            plugins: root.join("plugins"),
+            filename: ".tool-versions".to_string(),
+        }
+        .canonical();
+        for path in [real.join(".tool-versions"), link.join(".tool-versions")] {
+            assert_eq!(targets.classify(&path), Some(WatchKind::ToolVersions));
+        }
+        // A removed file still resolves through its directory.
+        std::fs::remove_file(real.join(".tool-versions")).unwrap();
+        assert_eq!(
+            targets.classify(&link.join(".tool-versions")),
+            Some(WatchKind::ToolVersions)
+        );
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -37,6 +37,7 @@ export function ReshimPanel() {
     }
     commands
       .listInstalled(selectedPlugin)
+      .then((entries) => entries.map((e) => e.version))
       .then((v) => {
         setVersions(v);
         setSelectedVersion(v.length > 0 ? v[v.length - 1] : "");
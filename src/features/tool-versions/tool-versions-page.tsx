@@ -7,7 +7,7 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { ToolVersionsEditor } from "./tool-versions-editor";
 import * as commands from "@/lib/commands";
-import type { ToolVersion, Plugin, SetScope } from "@/lib/types";
+import type { ToolVersion, Plugin, SetScope, VersionEntry } from "@/lib/types";
 
 export function ToolVersionsPage() {
   const { t } = useTranslation();
@@ -42,8 +42,8 @@ export function ToolVersionsPage() {
           list.map(async (p) => {
             const versions = await commands
               .listInstalled(p.name)
-              .catch(() => [] as string[]);
-            return [p.name, new Set(versions)] as const;
+              .catch(() => [] as VersionEntry[]);
+            return [p.name, new Set(versions.map((v) => v.version))] as const;
           }),
         ).then((results) => {
           setInstalledVersionsMap(new Map(results));
@@ -121,10 +121,10 @@ export function ToolVersionsPage() {
       // Refresh installed versions
       const versions = await commands
         .listInstalled(plugin)
-        .catch(() => [] as string[]);
+        .catch(() => [] as VersionEntry[]);
       setInstalledVersionsMap((prev) => {
         const next = new Map(prev);
-        next.set(plugin, new Set(versions));
+        next.set(plugin, new Set(versions.map((v) => v.version)));
         return next;
       });
     } catch (e) {
@@ -78,7 +78,10 @@ export function VersionsPage() {
     setAvailableLoading(true);
 
     const [installedResult, currentResult, latestResult] = await Promise.all([
-      commands.listInstalled(name).catch(() => [] as string[]),
+      commands
+        .listInstalled(name)
+        .then((vs) => vs.map((v) => v.version))
+        .catch(() => [] as string[]),
       commands
         .currentVersions(name)
         .then((vs) => (vs.length > 0 ? vs[0].version : null))
@@ -94,7 +97,7 @@ export function VersionsPage() {
     // Load available separately — can be slow
     commands
       .listAll(name)
-      .then(setAvailable)
+      .then((vs) => setAvailable(vs.map((v) => v.version)))
       .catch(() => setAvailable([]))
       .finally(() => setAvailableLoading(false));
   }, []);
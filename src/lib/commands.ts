@@ -1,4 +1,5 @@
 import { invoke, Channel } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type {
   Plugin,
   PluginRegistry,
@@ -11,6 +12,35 @@ import type {
   SetScope,
   InstallEvent,
   AppConfig,
+  LintDiagnostic,
+  ToolVersionsScan,
+  ResolutionExplanation,
+  ToolVersionsDiff,
+  MergeStrategy,
+  WatchEvent,
+  ExportTarget,
+  ExportResult,
+  ToolVersionsSuggestion,
+  BatchEvent,
+  BatchSummary,
+  ProjectInstallPlan,
+  UpgradePolicy,
+  UpgradePlan,
+  DiskUsage,
+  GcPlan,
+  UninstallImpact,
+  VersionEntry,
+  EolReport,
+  ReportFormat,
+  VersionQuery,
+  VersionPage,
+  DownloadEntry,
+  VersionMatrix,
+  DefaultPackagesFile,
+  ShellOverride,
+  ShellSyntax,
+  PluginDetails,
+  PluginDrift,
 } from "./types";
 
 // Info
@@ -27,27 +57,58 @@ export const pluginAdd = (name: string, gitUrl?: string) =>
   invoke<string>("plugin_add", { name, gitUrl });
 export const pluginRemove = (name: string) =>
   invoke<string>("plugin_remove", { name });
-export const pluginUpdate = (name?: string, all?: boolean) =>
-  invoke<string>("plugin_update", { name, all: all ?? false });
+export const pluginUpdate = (name?: string, all?: boolean, gitRef?: string) =>
+  invoke<string>("plugin_update", { name, all: all ?? false, gitRef });
+export const setPluginPin = (name: string, gitRef?: string) =>
+  invoke<void>("set_plugin_pin", { name, gitRef });
+export const pluginDrift = () => invoke<PluginDrift[]>("plugin_drift");
+export const pluginDetails = (name: string) =>
+  invoke<PluginDetails>("plugin_details", { name });
+export const defaultPackagesFiles = () =>
+  invoke<DefaultPackagesFile[]>("default_packages_files");
+export const readDefaultPackages = (plugin: string) =>
+  invoke<DefaultPackagesFile>("read_default_packages", { plugin });
+export const writeDefaultPackages = (plugin: string, packages: string[]) =>
+  invoke<DefaultPackagesFile>("write_default_packages", { plugin, packages });
 
 // Versions
 export const currentVersions = (name?: string) =>
   invoke<CurrentVersion[]>("current", { name });
-export const uninstallVersion = (name: string, version: string) =>
-  invoke<string>("uninstall", { name, version });
+export const uninstallImpact = (
+  name: string,
+  version: string,
+  roots: string[] = [],
+) => invoke<UninstallImpact>("uninstall_impact", { name, version, roots });
+export const uninstallVersion = (
+  name: string,
+  version: string,
+  force = false,
+) => invoke<string>("uninstall", { name, version, force });
 export const setVersion = (name: string, versions: string[], scope: SetScope) =>
   invoke<string>("set_version", { name, versions, scope });
+export const shellOverrides = (all = false) =>
+  invoke<ShellOverride[]>("shell_overrides", { all });
+export const unsetShellOverride = (name?: string) =>
+  invoke<boolean>("unset_shell_override", { name });
+export const exportShellOverrides = (syntax: ShellSyntax) =>
+  invoke<string>("export_shell_overrides", { syntax });
 export const latestVersion = (name: string, filter?: string) =>
   invoke<string>("latest", { name, filter });
 export const latestAll = () => invoke<LatestInfo[]>("latest_all");
 export const listInstalled = (name: string) =>
-  invoke<string[]>("list_installed", { name });
+  invoke<VersionEntry[]>("list_installed", { name });
 export const listAll = (name: string, filter?: string) =>
-  invoke<string[]>("list_all", { name, filter });
+  invoke<VersionEntry[]>("list_all", { name, filter });
+export const queryVersions = (name: string, query: VersionQuery = {}) =>
+  invoke<VersionPage>("query_versions", { name, query });
 export const whereInstalled = (name: string, version?: string) =>
   invoke<string>("where_installed", { name, version });
+export const explainResolution = (tool: string, dir?: string) =>
+  invoke<ResolutionExplanation>("explain_resolution", { dir, tool });
 
 // Versions — streaming install
+export const versionMatrix = (roots: string[] = []) =>
+  invoke<VersionMatrix>("version_matrix", { roots });
 export function installVersion(
   name: string | null,
   version: string | null,
@@ -66,6 +127,41 @@ export function installVersion(
   });
 }
 
+// Versions — project install
+export const planProjectInstall = (dir?: string) =>
+  invoke<ProjectInstallPlan>("plan_project_install", { dir });
+
+export function installProject(
+  dir: string | null,
+  addPlugins: boolean,
+  keepDownload: boolean,
+  onEvent: (event: BatchEvent) => void,
+): Promise<BatchSummary> {
+  const channel = new Channel<BatchEvent>();
+  channel.onmessage = onEvent;
+  return invoke<BatchSummary>("install_project", {
+    dir,
+    addPlugins,
+    keepDownload,
+    onEvent: channel,
+  });
+}
+export function installMany(
+  pairs: [string, string][],
+  addPlugins: boolean,
+  keepDownload: boolean,
+  onEvent: (event: BatchEvent) => void,
+): Promise<BatchSummary> {
+  const channel = new Channel<BatchEvent>();
+  channel.onmessage = onEvent;
+  return invoke<BatchSummary>("install_many", {
+    pairs,
+    addPlugins,
+    keepDownload,
+    onEvent: channel,
+  });
+}
+
 // Shims
 export const whichCommand = (command: string) =>
   invoke<string>("which_command", { command });
@@ -79,9 +175,108 @@ export const readToolVersions = (path: string) =>
   invoke<ToolVersion[]>("read_tool_versions", { path });
 export const writeToolVersions = (path: string, entries: ToolVersion[]) =>
   invoke<void>("write_tool_versions", { path, entries });
+export const lintToolVersions = (path: string) =>
+  invoke<LintDiagnostic[]>("lint_tool_versions", { path });
+export const scanToolVersions = (root: string, ignoreGlobs: string[] = []) =>
+  invoke<ToolVersionsScan>("scan_tool_versions", { root, ignoreGlobs });
+export const diffToolVersions = (a: string, b: string) =>
+  invoke<ToolVersionsDiff>("diff_tool_versions", { a, b });
+export const mergeToolVersions = (
+  a: string,
+  b: string,
+  strategy: MergeStrategy,
+  output: string,
+) =>
+  invoke<ToolVersion[]>("merge_tool_versions", { a, b, strategy, output });
+export const planUpgrades = (path: string, policy: UpgradePolicy) =>
+  invoke<UpgradePlan>("plan_upgrades", { path, policy });
+export function applyUpgradePlan(
+  path: string,
+  upgrades: [string, string][],
+  install: boolean,
+  onEvent: (event: BatchEvent) => void,
+): Promise<BatchSummary | null> {
+  const channel = new Channel<BatchEvent>();
+  channel.onmessage = onEvent;
+  return invoke<BatchSummary | null>("apply_upgrade_plan", {
+    path,
+    upgrades,
+    install,
+    onEvent: channel,
+  });
+}
 export const getToolVersionsPath = (scope: SetScope) =>
   invoke<string>("get_tool_versions_path", { scope });
 
+// Ecosystem version files
+export const exportToolVersions = (
+  path: string,
+  targets: ExportTarget[],
+  overwrite = false,
+  dryRun = false,
+) =>
+  invoke<ExportResult[]>("export_tool_versions", {
+    path,
+    targets,
+    overwrite,
+    dryRun,
+  });
+export const suggestToolVersions = (dir: string) =>
+  invoke<ToolVersionsSuggestion>("suggest_tool_versions", { dir });
+
+// End-of-life
+export const eolReport = (roots: string[] = [], withinDays = 90) =>
+  invoke<EolReport>("eol_report", { roots, withinDays });
+export const exportEolReport = (report: EolReport, format: ReportFormat) =>
+  invoke<string>("export_eol_report", { report, format });
+
+// Disk
+export const diskUsage = () => invoke<DiskUsage>("disk_usage");
+export const gcPlan = (roots: string[] = []) =>
+  invoke<GcPlan>("gc_plan", { roots });
+export function gcApply(
+  versions: [string, string][],
+  force: boolean,
+  onEvent: (event: BatchEvent) => void,
+): Promise<BatchSummary> {
+  const channel = new Channel<BatchEvent>();
+  channel.onmessage = onEvent;
+  return invoke<BatchSummary>("gc_apply", {
+    versions,
+    force,
+    onEvent: channel,
+  });
+}
+
+export const listDownloads = () => invoke<DownloadEntry[]>("list_downloads");
+export const deleteDownloads = (items: [string, string][]) =>
+  invoke<number>("delete_downloads", { items });
+export const pruneDownloads = () => invoke<DownloadEntry[]>("prune_downloads");
+export function reinstallFromDownload(
+  name: string,
+  version: string,
+  force: boolean,
+  onOutput: (event: InstallEvent) => void,
+): Promise<void> {
+  const channel = new Channel<InstallEvent>();
+  channel.onmessage = onOutput;
+  return invoke<void>("reinstall_from_download", {
+    name,
+    version,
+    force,
+    onOutput: channel,
+  });
+}
+
+// File watcher
+export const onAsdfChanged = (handler: (event: WatchEvent) => void) =>
+  listen<WatchEvent>("asdf-changed", (e) => handler(e.payload));
+export const onWatcherError = (handler: (message: string) => void) =>
+  listen<string>("asdf-watch-error", (e) => handler(e.payload));
+export const watcherError = () => invoke<string | null>("watcher_error");
+export const releaseMetadataError = () =>
+  invoke<string | null>("release_metadata_error");
+
 // Settings
 export const readConfig = () => invoke<AppConfig>("read_config");
 export const writeConfig = (config: AppConfig) =>
@@ -14,6 +14,19 @@ export interface CurrentVersion {
   version: string;
   source: string;
   installed: boolean;
+  release: ReleaseInfo | null;
+}
+
+export interface ReleaseInfo {
+  cycle: string;
+  lts: boolean;
+  release_date: string | null;
+  eol: string | null;
+}
+
+export interface VersionEntry {
+  version: string;
+  release: ReleaseInfo | null;
 }
 
 export interface LatestInfo {
@@ -47,13 +60,289 @@ export interface ToolVersion {
   versions: string[];
 }
 
-export type SetScope = "Local" | "Home" | "Parent";
+export interface ToolVersionsFile {
+  path: string;
+  entries: ToolVersion[];
+}
+
+export interface ToolPin {
+  tool: string;
+  version: string;
+  paths: string[];
+}
+
+export interface ToolVersionsScan {
+  files: ToolVersionsFile[];
+  pins: ToolPin[];
+}
+
+export type ResolutionSourceKind =
+  | "EnvVar"
+  | "ToolVersions"
+  | "LegacyFile"
+  | "Home";
+
+export interface ResolutionCandidate {
+  kind: ResolutionSourceKind;
+  location: string;
+  exists: boolean;
+  matched: boolean;
+  versions: string[];
+}
+
+export interface ResolutionExplanation {
+  tool: string;
+  dir: string;
+  candidates: ResolutionCandidate[];
+  winner: number | null;
+}
+
+export interface ToolVersionChange {
+  tool: string;
+  left: string[];
+  right: string[];
+}
+
+export interface ToolVersionsDiff {
+  added: ToolVersion[];
+  removed: ToolVersion[];
+  changed: ToolVersionChange[];
+}
+
+export type MergeStrategy = "PreferLeft" | "PreferRight" | "PreferNewer";
+
+export type ExportTarget =
+  | "Nvmrc"
+  | "PythonVersion"
+  | "RustToolchain"
+  | "MiseToml"
+  | "GoMod";
+
+export type ExportStatus =
+  | "Created"
+  | "Updated"
+  | "Unchanged"
+  | "Conflict"
+  | "Skipped";
+
+export interface ExportResult {
+  target: ExportTarget;
+  path: string;
+  status: ExportStatus;
+  conflicts: string[];
+  content: string | null;
+}
+
+export interface ToolSuggestion {
+  tool: string;
+  source: string;
+  constraint: string;
+  resolved: string | null;
+  selected: boolean;
+  note: string | null;
+}
+
+export interface ToolVersionsSuggestion {
+  suggestions: ToolSuggestion[];
+  entries: ToolVersion[];
+  content: string;
+}
+
+export type SetScope = "Local" | "Home" | "Parent" | "Shell";
+
+export type LintSeverity = "Error" | "Warning" | "Info";
+
+export interface LintDiagnostic {
+  line: number;
+  severity: LintSeverity;
+  tool: string | null;
+  message: string;
+}
+
+export type WatchKind =
+  | "ToolVersions"
+  | "HomeToolVersions"
+  | "Asdfrc"
+  | "Installs"
+  | "Plugins";
+
+export interface WatchEvent {
+  kind: WatchKind;
+  paths: string[];
+}
 
 export type InstallEvent =
   | { Stdout: string }
   | { Stderr: string }
   | { Finished: { success: boolean } };
 
+export type JobStatus = "Running" | "Succeeded" | "Failed" | "Skipped";
+
+export interface JobUpdate {
+  tool: string;
+  version: string;
+  status: JobStatus;
+  message: string | null;
+}
+
+export interface BatchSummary {
+  succeeded: number;
+  failed: number;
+  skipped: number;
+  jobs: JobUpdate[];
+}
+
+export type BatchEvent =
+  | { Plugin: { name: string; success: boolean; message: string | null } }
+  | { Job: JobUpdate }
+  | { Output: { tool: string; version: string; event: InstallEvent } }
+  | { Summary: BatchSummary };
+
+export interface ProjectInstallPlan {
+  path: string | null;
+  missing_plugins: string[];
+  missing: [string, string][];
+  default_packages: DefaultPackagesFile[];
+}
+
+export interface DefaultPackagesFile {
+  plugin: string;
+  path: string;
+  exists: boolean;
+  packages: string[];
+}
+
+export type UpgradePolicy = "Patch" | "Minor" | "Major";
+
+export interface UpgradeCandidate {
+  tool: string;
+  current: string;
+  target: string | null;
+}
+
+export interface UpgradePlan {
+  path: string;
+  policy: UpgradePolicy;
+  upgrades: UpgradeCandidate[];
+}
+
+export interface VersionUsage {
+  version: string;
+  installed: boolean;
+  install_bytes: number;
+  download_bytes: number;
+  modified: number | null;
+  last_used: number | null;
+}
+
+export interface PluginUsage {
+  name: string;
+  plugin_bytes: number;
+  install_bytes: number;
+  download_bytes: number;
+  versions: VersionUsage[];
+}
+
+export interface DiskUsage {
+  data_dir: string;
+  installs_bytes: number;
+  downloads_bytes: number;
+  plugins_bytes: number;
+  total_bytes: number;
+  plugins: PluginUsage[];
+}
+
+export type SearchMode = "Substring" | "Regex" | "Fuzzy";
+
+export type SortOrder = "Ascending" | "Descending";
+
+export interface VersionQuery {
+  search?: string | null;
+  mode?: SearchMode;
+  include_prerelease?: boolean;
+  group_by_major?: boolean;
+  order?: SortOrder;
+  offset?: number;
+  limit?: number | null;
+}
+
+export interface VersionGroup {
+  major: string;
+  count: number;
+  newest: string;
+}
+
+export interface VersionPage {
+  total: number;
+  offset: number;
+  versions: VersionEntry[];
+  groups: VersionGroup[];
+}
+
+export type EolStatus = "Expired" | "ExpiringSoon";
+
+export interface EolEntry {
+  tool: string;
+  version: string;
+  cycle: string;
+  eol: string;
+  days_left: number;
+  status: EolStatus;
+  files: string[];
+}
+
+export interface EolReport {
+  date: string;
+  within_days: number;
+  files: string[];
+  entries: EolEntry[];
+}
+
+export type ReportFormat = "Markdown" | "Json";
+
+export interface UninstallImpact {
+  tool: string;
+  version: string;
+  files: string[];
+  env_vars: string[];
+  is_global: boolean;
+}
+
+export interface MatrixCell {
+  version: string;
+  source: string;
+  installed: boolean;
+}
+
+export interface VersionMatrix {
+  tools: string[];
+  projects: string[];
+  cells: (MatrixCell | null)[][];
+  missing: number;
+  skipped_roots: string[];
+}
+
+export interface DownloadEntry {
+  tool: string;
+  version: string;
+  bytes: number;
+  modified: number | null;
+  installed: boolean;
+}
+
+export interface GcCandidate {
+  tool: string;
+  version: string;
+  bytes: number;
+  last_used: number | null;
+}
+
+export interface GcPlan {
+  files: string[];
+  candidates: GcCandidate[];
+  total_bytes: number;
+}
+
 export interface AppConfig {
   language: string;
   theme: string;
@@ -62,6 +351,8 @@ export interface AppConfig {
   keep_downloads: boolean;
   notifications: boolean;
   recent_projects: RecentProject[];
+  scan_roots: string[];
+  plugin_pins: Record<string, string>;
 }
 
 export interface RecentProject {
@@ -69,3 +360,46 @@ export interface RecentProject {
   name: string;
   last_opened: number;
 }
+
+export interface ShellOverride {
+  project: string;
+  tool: string;
+  env_var: string;
+  version: string;
+}
+
+export type ShellSyntax = "Posix" | "Fish" | "PowerShell";
+
+export interface GitHead {
+  commit: string;
+  branch: string | null;
+  tags: string[];
+  date: string;
+  message: string;
+}
+
+export interface PluginDetails {
+  name: string;
+  path: string;
+  head: GitHead | null;
+  remote_url: string | null;
+  dirty: boolean;
+  callbacks: string[];
+  missing_callbacks: string[];
+  readme: string | null;
+}
+
+export type DriftStatus =
+  | "Unpinned"
+  | "Matches"
+  | "Drifted"
+  | "Unresolved"
+  | "NotInstalled";
+
+export interface PluginDrift {
+  name: string;
+  pin: string | null;
+  pin_commit: string | null;
+  head: GitHead | null;
+  status: DriftStatus;
+}